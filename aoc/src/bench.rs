@@ -0,0 +1,439 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aoc_common::Timings;
+use clap::Args;
+use toml::{Table, Value};
+
+use crate::{solutions, Result};
+
+/// Changes smaller than this are treated as noise and never flagged, whatever the percentage.
+const NOISE_FLOOR: Duration = Duration::from_micros(10);
+
+#[derive(Args)]
+pub struct Options {
+    /// Only benchmark this year
+    #[arg(short, long)]
+    year: Option<u16>,
+    /// Only benchmark this day
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// How many times to parse and solve each day
+    #[arg(short, long, default_value_t = 10)]
+    runs: usize,
+    /// Compare the medians against a baseline saved with `--save-baseline`
+    #[arg(short, long)]
+    baseline: Option<PathBuf>,
+    /// Record the medians in this baseline file, keeping the entries of days not benchmarked
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+    /// How many percent slower than the baseline a stage can get before it is flagged
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// Also write the table to this file, e.g. `bench_output.txt`
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    const ALL: [Stage; 3] = [Stage::Parse, Stage::Part1, Stage::Part2];
+
+    fn key(&self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        }
+    }
+
+    fn of(&self, timings: &Timings) -> Duration {
+        match self {
+            Stage::Parse => timings.parse,
+            Stage::Part1 => timings.part1,
+            Stage::Part2 => timings.part2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = match samples.len() % 2 {
+            0 => (samples[middle - 1] + samples[middle]) / 2,
+            _ => samples[middle],
+        };
+
+        Self {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// How much slower `current` is than `baseline` in percent, when that is more than `threshold`.
+fn regression(current: Duration, baseline: Duration, threshold: f64) -> Option<f64> {
+    if current.saturating_sub(baseline) < NOISE_FLOOR {
+        return None;
+    }
+
+    let change = percent_change(current, baseline);
+
+    (change > threshold).then_some(change)
+}
+
+fn percent_change(current: Duration, baseline: Duration) -> f64 {
+    (current.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0
+}
+
+fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+
+    match nanos {
+        0..=999 => format!("{nanos}ns"),
+        1_000..=999_999 => format!("{:.1}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", duration.as_secs_f64()),
+    }
+}
+
+/// The median time of each stage from a previous run, in nanoseconds.
+///
+/// ```toml
+/// [2022.day7]
+/// parse = 152301
+/// part1 = 20870
+/// part2 = 21453
+/// ```
+#[derive(Default)]
+struct Baseline {
+    years: Table,
+}
+
+impl Baseline {
+    fn load(path: &Path) -> std::result::Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("could not read baseline {}: {err}", path.display()))?;
+
+        Self::parse(&content)
+            .map_err(|err| format!("could not parse baseline {}: {err}", path.display()))
+    }
+
+    fn parse(content: &str) -> std::result::Result<Self, toml::de::Error> {
+        Ok(Self {
+            years: content.parse()?,
+        })
+    }
+
+    fn get(&self, year: u16, day: u8, stage: Stage) -> Option<Duration> {
+        let nanos = self
+            .years
+            .get(&year.to_string())?
+            .get(format!("day{day}"))?
+            .get(stage.key())?
+            .as_integer()?;
+
+        Some(Duration::from_nanos(nanos.try_into().ok()?))
+    }
+
+    fn record(&mut self, year: u16, day: u8, stage: Stage, median: Duration) {
+        let days = self
+            .years
+            .entry(year.to_string())
+            .or_insert_with(|| Value::Table(Table::new()));
+
+        let Value::Table(days) = days else {
+            return;
+        };
+
+        let stages = days
+            .entry(format!("day{day}"))
+            .or_insert_with(|| Value::Table(Table::new()));
+
+        if let Value::Table(stages) = stages {
+            let nanos = i64::try_from(median.as_nanos()).unwrap_or(i64::MAX);
+
+            stages.insert(stage.key().to_string(), Value::Integer(nanos));
+        }
+    }
+}
+
+struct Row {
+    year: u16,
+    day: u8,
+    /// The statistics of each stage, or `None` when the day has no input.
+    stages: Option<Vec<(Stage, Stats)>>,
+}
+
+fn render(rows: &[Row], baseline: Option<&Baseline>, threshold: f64) -> (String, usize) {
+    let mut table = String::new();
+    let mut regressions = 0;
+
+    let _ = writeln!(
+        table,
+        "year  day  stage  {:>9}  {:>9}  {:>9}  {:>9}  change",
+        "min", "median", "max", "baseline"
+    );
+
+    for row in rows {
+        let Some(stages) = &row.stages else {
+            let _ = writeln!(table, "{:<4}  {:>3}  no input", row.year, row.day);
+            continue;
+        };
+
+        for (stage, stats) in stages {
+            let previous = baseline.and_then(|baseline| baseline.get(row.year, row.day, *stage));
+
+            let (previous_label, change) = match previous {
+                Some(previous) => {
+                    let change = format!("{:+.1}%", percent_change(stats.median, previous));
+
+                    match regression(stats.median, previous, threshold) {
+                        Some(_) => {
+                            regressions += 1;
+                            (format_duration(previous), format!("{change} REGRESSED"))
+                        }
+                        None => (format_duration(previous), change),
+                    }
+                }
+                None => (String::new(), String::new()),
+            };
+
+            let line = format!(
+                "{:<4}  {:>3}  {:<5}  {:>9}  {:>9}  {:>9}  {:>9}  {change}",
+                row.year,
+                row.day,
+                stage.key(),
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max),
+                previous_label,
+            );
+
+            let _ = writeln!(table, "{}", line.trim_end());
+        }
+    }
+
+    (table, regressions)
+}
+
+pub fn bench(options: Options) -> Result<()> {
+    if options.runs == 0 {
+        return Err("--runs must be at least 1".into());
+    }
+
+    if cfg!(debug_assertions) {
+        eprintln!("warning: benchmarking a debug build, pass --release for meaningful timings");
+    }
+
+    let baseline = options
+        .baseline
+        .as_deref()
+        .map(Baseline::load)
+        .transpose()?;
+    let mut rows = vec![];
+
+    let years = solutions::YEARS
+        .iter()
+        .filter(|entry| options.year.is_none_or(|year| year == entry.year));
+
+    for entry in years {
+        let inputs = (entry.inputs)();
+
+        let puzzles = entry
+            .solutions
+            .iter()
+            .filter(|puzzle| options.day.is_none_or(|day| day == puzzle.day()));
+
+        for puzzle in puzzles {
+            let Ok(input) = inputs.load(puzzle.day()) else {
+                rows.push(Row {
+                    year: entry.year,
+                    day: puzzle.day(),
+                    stages: None,
+                });
+
+                continue;
+            };
+
+            let timings = (0..options.runs)
+                .map(|_| puzzle.time(&input, &[]))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|err| format!("{} day {}: {err}", entry.year, puzzle.day()))?;
+
+            let stages = Stage::ALL
+                .into_iter()
+                .map(|stage| {
+                    let mut samples = timings
+                        .iter()
+                        .map(|timings| stage.of(timings))
+                        .collect::<Vec<_>>();
+
+                    (stage, Stats::from_samples(&mut samples))
+                })
+                .collect();
+
+            rows.push(Row {
+                year: entry.year,
+                day: puzzle.day(),
+                stages: Some(stages),
+            });
+        }
+    }
+
+    if rows.is_empty() {
+        return Err("no solutions match the given year and day".into());
+    }
+
+    let (table, regressions) = render(&rows, baseline.as_ref(), options.threshold);
+
+    print!("{table}");
+
+    if let Some(path) = &options.output {
+        fs::write(path, &table)
+            .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+    }
+
+    if let Some(path) = &options.save_baseline {
+        save_baseline(path, &rows)?;
+    }
+
+    match regressions {
+        0 => Ok(()),
+        1 => Err("1 stage regressed against the baseline".into()),
+        regressions => Err(format!("{regressions} stages regressed against the baseline").into()),
+    }
+}
+
+fn save_baseline(path: &Path, rows: &[Row]) -> Result<()> {
+    let mut baseline = match path.exists() {
+        true => Baseline::load(path)?,
+        false => Baseline::default(),
+    };
+
+    for row in rows {
+        for (stage, stats) in row.stages.iter().flatten() {
+            baseline.record(row.year, row.day, *stage, stats.median);
+        }
+    }
+
+    fs::write(path, baseline.years.to_string())
+        .map_err(|err| format!("could not write baseline {}: {err}", path.display()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let mut odd = [millis(5), millis(1), millis(3)];
+        let mut even = [millis(4), millis(1), millis(2), millis(8)];
+
+        assert_eq!(
+            Stats {
+                min: millis(1),
+                median: millis(3),
+                max: millis(5)
+            },
+            Stats::from_samples(&mut odd)
+        );
+        assert_eq!(millis(3), Stats::from_samples(&mut even).median);
+    }
+
+    #[test]
+    fn format_durations() {
+        assert_eq!("850ns", format_duration(Duration::from_nanos(850)));
+        assert_eq!("152.3µs", format_duration(Duration::from_nanos(152_301)));
+        assert_eq!("20.87ms", format_duration(Duration::from_micros(20_870)));
+        assert_eq!("3.50s", format_duration(millis(3_500)));
+    }
+
+    #[test]
+    fn flags_regressions_over_threshold() {
+        assert_eq!(Some(50.0), regression(millis(15), millis(10), 10.0));
+        assert_eq!(None, regression(millis(105), millis(100), 10.0));
+        assert_eq!(None, regression(millis(5), millis(10), 10.0));
+    }
+
+    #[test]
+    fn ignores_changes_below_noise_floor() {
+        let baseline = Duration::from_nanos(100);
+
+        assert_eq!(None, regression(Duration::from_nanos(900), baseline, 10.0));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.record(2022, 7, Stage::Parse, Duration::from_nanos(152_301));
+        baseline.record(2022, 7, Stage::Part2, millis(2));
+
+        let baseline = Baseline::parse(&baseline.years.to_string()).unwrap();
+
+        assert_eq!(
+            Some(Duration::from_nanos(152_301)),
+            baseline.get(2022, 7, Stage::Parse)
+        );
+        assert_eq!(Some(millis(2)), baseline.get(2022, 7, Stage::Part2));
+        assert_eq!(None, baseline.get(2022, 7, Stage::Part1));
+        assert_eq!(None, baseline.get(2015, 7, Stage::Parse));
+    }
+
+    #[test]
+    fn render_flags_regressed_stages() {
+        let stats = Stats {
+            min: millis(10),
+            median: millis(20),
+            max: millis(30),
+        };
+        let rows = [
+            Row {
+                year: 2022,
+                day: 7,
+                stages: Some(vec![(Stage::Parse, stats), (Stage::Part1, stats)]),
+            },
+            Row {
+                year: 2022,
+                day: 8,
+                stages: None,
+            },
+        ];
+
+        let mut baseline = Baseline::default();
+        baseline.record(2022, 7, Stage::Parse, millis(10));
+        baseline.record(2022, 7, Stage::Part1, millis(20));
+
+        let (table, regressions) = render(&rows, Some(&baseline), 10.0);
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(1, regressions);
+        assert!(lines[1].ends_with("+100.0% REGRESSED"));
+        assert!(lines[2].ends_with("+0.0%"));
+        assert_eq!("2022    8  no input", lines[3]);
+    }
+}
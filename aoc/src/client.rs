@@ -0,0 +1,244 @@
+use std::{
+    cell::Cell,
+    fmt, thread,
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the tool to the Advent of Code servers, as their maintainer asks automated tools to.
+pub const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (personal Advent of Code runner, rust ureq)"
+);
+
+/// The shortest gap left between two requests, so fetching a whole year stays polite.
+pub const DEFAULT_DELAY: Duration = Duration::from_secs(3);
+
+fn parse_delay(seconds: &str) -> Result<Duration, String> {
+    seconds
+        .parse::<f64>()
+        .ok()
+        .filter(|&seconds| seconds >= 1.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("expected at least 1 second, got `{seconds}`"))
+}
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Args)]
+#[group(skip)]
+pub struct Options {
+    /// The session cookie of a logged in adventofcode.com account
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+    /// Talk to this server instead of adventofcode.com
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+    /// How many seconds to wait between two requests
+    #[arg(long, value_parser = parse_delay, default_value = "3")]
+    delay: Duration,
+}
+
+impl Options {
+    #[cfg(test)]
+    pub fn new(session: Option<&str>, base_url: &str) -> Self {
+        Self {
+            session: session.map(str::to_string),
+            base_url: base_url.to_string(),
+            delay: DEFAULT_DELAY,
+        }
+    }
+
+    pub fn client(&self) -> Result<Client, String> {
+        match &self.session {
+            Some(session) => Ok(Client::new(&self.base_url, session).with_delay(self.delay)),
+            None => {
+                Err("set AOC_SESSION to the session cookie of your adventofcode.com login".into())
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The server answered with something other than `200 OK`.
+    Status {
+        status: u16,
+        body: String,
+    },
+    Transport(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Status { status, body } => {
+                write!(f, "server answered with status {status}")?;
+
+                match body.lines().next().map(str::trim) {
+                    Some(line) if !line.is_empty() => write!(f, ": {line}"),
+                    _ => Ok(()),
+                }
+            }
+            ClientError::Transport(err) => write!(f, "request failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<ureq::Error> for ClientError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status, response) => ClientError::Status {
+                status,
+                body: response.into_string().unwrap_or_default(),
+            },
+            ureq::Error::Transport(err) => ClientError::Transport(err.to_string()),
+        }
+    }
+}
+
+/// A logged in session with the Advent of Code website, which never sends requests closer
+/// together than its delay.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    delay: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(TIMEOUT)
+                .build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            delay: DEFAULT_DELAY,
+            last_request: Cell::new(None),
+        }
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, ClientError> {
+        self.throttle();
+
+        let response = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .call()?;
+
+        response
+            .into_string()
+            .map_err(|err| ClientError::Transport(err.to_string()))
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, ClientError> {
+        self.throttle();
+
+        let response = self
+            .agent
+            .post(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .send_form(fields)?;
+
+        response
+            .into_string()
+            .map_err(|err| ClientError::Transport(err.to_string()))
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Sleeps until the delay since the previous request has passed.
+    fn throttle(&self) {
+        if let Some(last) = self.last_request.get() {
+            if let Some(wait) = self.delay.checked_sub(last.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+
+        self.last_request.set(Some(Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_server::MockServer;
+
+    use super::*;
+
+    #[test]
+    fn sends_session_and_user_agent() {
+        let server = MockServer::start(vec![(200, "1000\n2000\n".to_string())]);
+        let client = Client::new(&format!("{}/", server.url()), " abc123\n");
+
+        assert_eq!("1000\n2000\n", client.get("/2022/day/1/input").unwrap());
+
+        let requests = server.requests();
+
+        assert_eq!("GET", requests[0].method);
+        assert_eq!("/2022/day/1/input", requests[0].path);
+        assert_eq!(Some("session=abc123"), requests[0].header("cookie"));
+        assert_eq!(Some(USER_AGENT), requests[0].header("user-agent"));
+    }
+
+    #[test]
+    fn posts_forms() {
+        let server = MockServer::start(vec![(200, "ok".to_string())]);
+        let client = Client::new(&server.url(), "abc123");
+
+        client
+            .post_form("/2022/day/1/answer", &[("level", "1"), ("answer", "24000")])
+            .unwrap();
+
+        let requests = server.requests();
+
+        assert_eq!("POST", requests[0].method);
+        assert_eq!("level=1&answer=24000", requests[0].body);
+    }
+
+    #[test]
+    fn reports_error_statuses() {
+        let server = MockServer::start(vec![(404, "Not found\n".to_string())]);
+        let client = Client::new(&server.url(), "abc123");
+
+        let err = client.get("/2022/day/26/input").unwrap_err();
+
+        assert_eq!(
+            "server answered with status 404: Not found",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let server = MockServer::start(vec![(200, String::new()), (200, String::new())]);
+        let client = Client::new(&server.url(), "abc123").with_delay(Duration::from_millis(100));
+
+        let start = Instant::now();
+        client.get("/").unwrap();
+        client.get("/").unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        assert_eq!(2, server.requests().len());
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::Inputs;
+use clap::Args;
+
+use crate::{
+    client::{self, Client},
+    solutions, Result,
+};
+
+#[derive(Args)]
+pub struct Options {
+    year: u16,
+    /// Only fetch this day, whether it has a solution yet or not
+    day: Option<u8>,
+    /// Store the inputs in this directory instead of the year's input directory
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+    #[command(flatten)]
+    client: client::Options,
+}
+
+/// Downloads the input for `day` of `year` into `inputs`.
+///
+/// The file is written under a temporary name first and renamed once complete, so an interrupted
+/// download is never mistaken for a cached input.
+pub fn download(client: &Client, inputs: &Inputs, year: u16, day: u8) -> Result<PathBuf> {
+    let path = inputs.path(day);
+    let input = client
+        .get(&format!("/{year}/day/{day}/input"))
+        .map_err(|err| format!("could not download {year} day {day}: {err}"))?;
+
+    fs::create_dir_all(inputs.dir())
+        .map_err(|err| format!("could not create {}: {err}", inputs.dir().display()))?;
+
+    let partial = path.with_extension("txt.partial");
+
+    fs::write(&partial, input)
+        .and_then(|_| fs::rename(&partial, &path))
+        .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+
+    Ok(path)
+}
+
+/// The inputs of `year`, from `input_dir` when given and the year's own directory otherwise.
+pub fn inputs(year: u16, input_dir: Option<&Path>) -> Result<Inputs> {
+    match (input_dir, solutions::year(year)) {
+        (Some(dir), _) => Ok(Inputs::new(dir)),
+        (None, Some(entry)) => Ok((entry.inputs)()),
+        (None, None) => Err(format!("{year} has no crate yet, pass --input-dir").into()),
+    }
+}
+
+pub fn fetch(options: Options) -> Result<()> {
+    let year = solutions::year(options.year);
+    let inputs = inputs(options.year, options.input_dir.as_deref())?;
+
+    let days = match (options.day, year) {
+        (Some(day @ 1..=25), _) => vec![day],
+        (Some(day), _) => return Err(format!("there is no day {day}, days go from 1 to 25").into()),
+        (None, Some(year)) => year.solutions.iter().map(|puzzle| puzzle.day()).collect(),
+        (None, None) => (1..=25).collect(),
+    };
+
+    // Only log in when something actually needs downloading.
+    let mut client = None;
+
+    for day in days {
+        let path = inputs.path(day);
+
+        if path.exists() {
+            println!("{} day {day}: cached at {}", options.year, path.display());
+            continue;
+        }
+
+        let client = match &mut client {
+            Some(client) => client,
+            None => client.insert(options.client.client()?),
+        };
+
+        let path = download(client, &inputs, options.year, day)?;
+
+        println!("{} day {day}: saved to {}", options.year, path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use crate::mock_server::MockServer;
+
+    use super::*;
+
+    fn temp_inputs(name: &str) -> Inputs {
+        let dir = env::temp_dir().join(format!("aoc_fetch_{name}_{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        Inputs::new(dir)
+    }
+
+    #[test]
+    fn downloads_into_input_dir() {
+        let server = MockServer::start(vec![(200, "1000\n2000\n".to_string())]);
+        let client = Client::new(&server.url(), "abc123");
+        let inputs = temp_inputs("download");
+
+        let path = download(&client, &inputs, 2022, 1).unwrap();
+
+        assert_eq!(inputs.path(1), path);
+        assert_eq!("1000\n2000\n", inputs.load(1).unwrap());
+        assert_eq!("/2022/day/1/input", server.requests()[0].path);
+        assert!(!path.with_extension("txt.partial").exists());
+
+        fs::remove_dir_all(inputs.dir()).unwrap();
+    }
+
+    #[test]
+    fn failed_downloads_leave_no_file() {
+        let server = MockServer::start(vec![(
+            400,
+            "Please don't repeatedly request this endpoint before it unlocks!".to_string(),
+        )]);
+        let client = Client::new(&server.url(), "abc123");
+        let inputs = temp_inputs("failed");
+
+        let err = download(&client, &inputs, 2030, 1).unwrap_err();
+
+        assert_eq!(
+            "could not download 2030 day 1: server answered with status 400: Please don't repeatedly request this endpoint before it unlocks!",
+            err.to_string()
+        );
+        assert!(!inputs.path(1).exists());
+    }
+
+    #[test]
+    fn never_downloads_cached_inputs() {
+        let server = MockServer::start(vec![]);
+        let inputs = temp_inputs("cached");
+        fs::create_dir_all(inputs.dir()).unwrap();
+        fs::write(inputs.path(3), "cached").unwrap();
+
+        let options = Options {
+            year: 2022,
+            day: Some(3),
+            input_dir: Some(inputs.dir().to_path_buf()),
+            client: client::Options::new(None, &server.url()),
+        };
+
+        fetch(options).unwrap();
+
+        assert!(server.requests().is_empty());
+        assert_eq!("cached", inputs.load(3).unwrap());
+
+        fs::remove_dir_all(inputs.dir()).unwrap();
+    }
+}
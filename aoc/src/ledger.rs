@@ -0,0 +1,323 @@
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_common::{Inputs, Part};
+use toml::{Table, Value};
+
+/// Why the website turned down a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Wrong,
+}
+
+impl Rejection {
+    pub const ALL: [Rejection; 3] = [Rejection::TooHigh, Rejection::TooLow, Rejection::Wrong];
+
+    fn key(self, part: Part) -> String {
+        let suffix = match self {
+            Rejection::TooHigh => "too_high",
+            Rejection::TooLow => "too_low",
+            Rejection::Wrong => "wrong",
+        };
+
+        format!("{}_{suffix}", part_key(part))
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Rejection::TooHigh => "too high",
+            Rejection::TooLow => "too low",
+            Rejection::Wrong => "wrong",
+        }
+    }
+}
+
+/// The known answers for one year, kept next to that year's puzzle inputs since both are specific
+/// to the user.
+///
+/// ```toml
+/// [day7]
+/// part1 = 1543140
+/// part2 = 1117448
+///
+/// [day5]
+/// part1 = "RTGWZTHLD"
+/// ```
+///
+/// `submit` also keeps the answers the website rejected, e.g. `part2_too_high = [45000]`, and the
+/// time before which it asked not to be sent another answer, as `next_submission` in seconds since
+/// the Unix epoch.
+#[derive(Default)]
+pub struct Ledger {
+    days: Table,
+}
+
+impl Ledger {
+    pub const FILE_NAME: &'static str = "answers.toml";
+
+    /// Loads the ledger of the year `inputs` belong to, starting an empty one if none exists yet.
+    pub fn load(inputs: &Inputs) -> Result<Self, String> {
+        Self::load_from(&inputs.dir().join(Self::FILE_NAME))
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(format!("could not read {}: {err}", path.display())),
+        };
+
+        let days = content
+            .parse::<Table>()
+            .map_err(|err| format!("could not parse {}: {err}", path.display()))?;
+
+        Ok(Self { days })
+    }
+
+    pub fn save(&self, inputs: &Inputs) -> Result<(), String> {
+        self.save_to(&inputs.dir().join(Self::FILE_NAME))
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let content = toml::to_string(&self.days)
+            .map_err(|err| format!("could not serialise {}: {err}", path.display()))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+        }
+
+        fs::write(path, content).map_err(|err| format!("could not write {}: {err}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<String> {
+        let answer = self.days.get(&day_key(day))?.get(part_key(part))?;
+
+        Some(text(answer))
+    }
+
+    /// Records the accepted answer for a part.
+    pub fn set(&mut self, day: u8, part: Part, answer: &str) {
+        self.day_mut(day)
+            .insert(part_key(part).to_string(), value(answer));
+    }
+
+    /// Records a rejected answer, unless it is already known to be wrong.
+    pub fn reject(&mut self, day: u8, part: Part, answer: &str, rejection: Rejection) {
+        if self.rejection(day, part, answer).is_some() {
+            return;
+        }
+
+        let guesses = self
+            .day_mut(day)
+            .entry(rejection.key(part))
+            .or_insert_with(|| Value::Array(vec![]));
+
+        if let Value::Array(guesses) = guesses {
+            guesses.push(value(answer));
+        }
+    }
+
+    /// Whether `answer` is known to be wrong, either because it was rejected before or because it
+    /// is beyond an earlier answer that was too high or too low.
+    pub fn rejection(&self, day: u8, part: Part, answer: &str) -> Option<Rejection> {
+        let answer = answer.trim();
+
+        let exact = Rejection::ALL.into_iter().find(|&rejection| {
+            self.guesses(day, part, rejection)
+                .any(|guess| guess == answer)
+        });
+
+        if exact.is_some() {
+            return exact;
+        }
+
+        let answer = answer.parse::<i128>().ok()?;
+        let numbers = |rejection| {
+            self.guesses(day, part, rejection)
+                .filter_map(|guess| guess.parse::<i128>().ok())
+        };
+
+        if numbers(Rejection::TooHigh).any(|guess| answer >= guess) {
+            Some(Rejection::TooHigh)
+        } else if numbers(Rejection::TooLow).any(|guess| answer <= guess) {
+            Some(Rejection::TooLow)
+        } else {
+            None
+        }
+    }
+
+    fn guesses(
+        &self,
+        day: u8,
+        part: Part,
+        rejection: Rejection,
+    ) -> impl Iterator<Item = String> + '_ {
+        self.days
+            .get(&day_key(day))
+            .and_then(|day| day.get(rejection.key(part)))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(text)
+    }
+
+    /// When the website said it will take another answer, if that is still to come.
+    pub fn next_submission(&self) -> Option<SystemTime> {
+        let seconds = self.days.get(NEXT_SUBMISSION)?.as_integer()?;
+        let time = UNIX_EPOCH + Duration::from_secs(seconds.try_into().ok()?);
+
+        (time > SystemTime::now()).then_some(time)
+    }
+
+    pub fn set_next_submission(&mut self, time: SystemTime) {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs_f64().ceil() as i64);
+
+        self.days
+            .insert(NEXT_SUBMISSION.to_string(), Value::Integer(seconds));
+    }
+
+    fn day_mut(&mut self, day: u8) -> &mut Table {
+        let day = self
+            .days
+            .entry(day_key(day))
+            .or_insert_with(|| Value::Table(Table::new()));
+
+        if !day.is_table() {
+            *day = Value::Table(Table::new());
+        }
+
+        day.as_table_mut().unwrap()
+    }
+}
+
+const NEXT_SUBMISSION: &str = "next_submission";
+
+fn text(answer: &Value) -> String {
+    match answer {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Stores numeric answers as numbers, so the ledger reads the same as one written by hand.
+fn value(answer: &str) -> Value {
+    let answer = answer.trim();
+
+    match answer.parse::<i64>() {
+        Ok(number) => Value::Integer(number),
+        Err(_) => Value::String(answer.to_string()),
+    }
+}
+
+fn day_key(day: u8) -> String {
+    format!("day{day}")
+}
+
+fn part_key(part: Part) -> &'static str {
+    match part {
+        Part::One => "part1",
+        Part::Two => "part2",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger(content: &str) -> Ledger {
+        Ledger {
+            days: content.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn get_recorded_answers() {
+        let ledger = ledger(
+            "[day7]
+            part1 = 1543140
+
+            [day5]
+            part1 = \"RTGWZTHLD\"",
+        );
+
+        assert_eq!(Some("1543140".to_string()), ledger.get(7, Part::One));
+        assert_eq!(None, ledger.get(7, Part::Two));
+        assert_eq!(Some("RTGWZTHLD".to_string()), ledger.get(5, Part::One));
+        assert_eq!(None, ledger.get(6, Part::One));
+    }
+
+    #[test]
+    fn known_rejections() {
+        let mut ledger = Ledger::default();
+
+        ledger.reject(1, Part::One, "70000", Rejection::TooHigh);
+        ledger.reject(1, Part::One, "100", Rejection::TooLow);
+        ledger.reject(1, Part::One, "500", Rejection::Wrong);
+        ledger.reject(5, Part::One, "CMZ", Rejection::Wrong);
+
+        assert_eq!(
+            Some(Rejection::TooHigh),
+            ledger.rejection(1, Part::One, "70000")
+        );
+        assert_eq!(
+            Some(Rejection::TooHigh),
+            ledger.rejection(1, Part::One, "80000")
+        );
+        assert_eq!(
+            Some(Rejection::TooLow),
+            ledger.rejection(1, Part::One, "99")
+        );
+        assert_eq!(
+            Some(Rejection::Wrong),
+            ledger.rejection(1, Part::One, " 500\n")
+        );
+        assert_eq!(None, ledger.rejection(1, Part::One, "501"));
+        assert_eq!(None, ledger.rejection(1, Part::Two, "70000"));
+        assert_eq!(
+            Some(Rejection::Wrong),
+            ledger.rejection(5, Part::One, "CMZ")
+        );
+        assert_eq!(None, ledger.rejection(5, Part::One, "MCD"));
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let path = std::env::temp_dir()
+            .join(format!("aoc_ledger_{}", std::process::id()))
+            .join(Ledger::FILE_NAME);
+        let mut ledger = ledger("[day7]\npart1 = 1543140");
+
+        ledger.set(7, Part::Two, "1117448");
+        ledger.set(5, Part::One, "RTGWZTHLD");
+        ledger.reject(5, Part::Two, "ABC", Rejection::Wrong);
+        ledger.save_to(&path).unwrap();
+
+        let saved = Ledger::load_from(&path).unwrap();
+
+        assert_eq!(Some("1543140".to_string()), saved.get(7, Part::One));
+        assert_eq!(Some("1117448".to_string()), saved.get(7, Part::Two));
+        assert_eq!(Some("RTGWZTHLD".to_string()), saved.get(5, Part::One));
+        assert_eq!(Some(Rejection::Wrong), saved.rejection(5, Part::Two, "ABC"));
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .contains("part2 = 1117448"));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn missing_ledger_is_empty() {
+        let ledger = Ledger::load(&Inputs::new("does/not/exist")).unwrap();
+
+        assert_eq!(None, ledger.get(1, Part::One));
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use aoc_common::{Answer, Part};
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod client;
+mod fetch;
+mod ledger;
+#[cfg(test)]
+mod mock_server;
+mod scaffold;
+mod solutions;
+mod submit;
+mod verify;
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(
+    name = "aoc",
+    about = "Run Advent of Code solutions from the command line"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every year, day and part that has a solution
+    List,
+    /// Solve one part of a puzzle and print the answer
+    Run {
+        year: u16,
+        day: u8,
+        part: Part,
+        /// Read the puzzle input from this file instead of stdin
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Override a puzzle parameter, e.g. `--param row=10`
+        #[arg(short, long = "param", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+    },
+    /// Check every solution against its input and the answers recorded in `answers.toml`
+    Verify {
+        /// Only verify this year
+        #[arg(short, long)]
+        year: Option<u16>,
+        /// Only verify this day
+        #[arg(short, long)]
+        day: Option<u8>,
+    },
+    /// Time the parsing and both parts of every solution over several runs
+    Bench(bench::Options),
+    /// Download the puzzle inputs of a year, or of one day, that are not saved yet
+    Fetch(fetch::Options),
+    /// Add a day from a template, creating the crate of its year if there is none yet
+    New(scaffold::Options),
+    /// Send an answer to the website, solving the part first when no answer is given
+    Submit(submit::Options),
+}
+
+fn parse_param(param: &str) -> std::result::Result<(String, String), String> {
+    param
+        .split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected `name=value`, got `{param}`"))
+}
+
+fn list() -> Result<()> {
+    for puzzle in solutions::all() {
+        let params = puzzle
+            .params()
+            .into_iter()
+            .map(|(name, value)| format!(" --param {name}={value}"))
+            .collect::<String>();
+
+        println!(
+            "{} day {:>2}: {}{params}",
+            puzzle.year(),
+            puzzle.day(),
+            puzzle.title()
+        );
+    }
+
+    Ok(())
+}
+
+fn read_input(path: Option<PathBuf>) -> Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(&path)
+            .map_err(|err| format!("could not read input file {}: {err}", path.display()).into()),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+
+            Ok(input)
+        }
+    }
+}
+
+fn run(
+    year: u16,
+    day: u8,
+    part: Part,
+    input: Option<PathBuf>,
+    params: &[(String, String)],
+) -> Result<()> {
+    let puzzle = solutions::find(year, day).ok_or_else(|| {
+        format!("{year} day {day} has not been solved yet (see `aoc list` for available solutions)")
+    })?;
+
+    let input = read_input(input)?;
+
+    let answer = puzzle
+        .solve(part, &input, params)
+        .map_err(|err| format!("{year} day {day}: {err}"))?;
+
+    match answer {
+        Answer::Unsolved => {
+            Err(format!("{year} day {day} part {part} found no answer for this input").into())
+        }
+        answer => {
+            println!("{answer}");
+
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::List => list(),
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            params,
+        } => run(year, day, part, input, &params),
+        Command::Verify { year, day } => verify::verify(year, day),
+        Command::Bench(options) => bench::bench(options),
+        Command::Fetch(options) => fetch::fetch(options),
+        Command::Submit(options) => submit::submit(options),
+        Command::New(options) => scaffold::new(options),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
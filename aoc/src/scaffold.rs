@@ -0,0 +1,415 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+
+use crate::Result;
+
+const DAY_TEMPLATE: &str = include_str!("templates/day.rs.template");
+const EXAMPLE_TEMPLATE: &str = include_str!("templates/example.toml.template");
+const LIB_TEMPLATE: &str = include_str!("templates/lib.rs.template");
+const MANIFEST_TEMPLATE: &str = include_str!("templates/Cargo.toml.template");
+
+#[derive(Args)]
+pub struct Options {
+    year: u16,
+    day: u8,
+    /// The puzzle's title, as shown by `aoc list`
+    #[arg(short, long)]
+    title: Option<String>,
+    /// The workspace to add the day to, instead of the one `aoc` was built from
+    #[arg(long)]
+    workspace: Option<PathBuf>,
+}
+
+/// Fills in the `{year}`, `{day}` and `{title}` placeholders of a template.
+fn render(template: &str, year: u16, day: u8, title: &str) -> String {
+    let title = title.replace('\\', "\\\\").replace('"', "\\\"");
+
+    template
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{title}", &title)
+}
+
+/// Inserts `entry` as a line of `text`, before the first line numbered above `key` or after the
+/// last numbered line, so numbered lists stay sorted. `number` reads the number of a line, if it
+/// has one.
+///
+/// The entry takes the line endings of `text`, since some of the crates use CRLF.
+fn insert_numbered(
+    text: &str,
+    key: u16,
+    entry: &str,
+    number: impl Fn(&str) -> Option<u16>,
+) -> Result<String> {
+    let newline = match text.contains("\r\n") {
+        true => "\r\n",
+        false => "\n",
+    };
+    let lines = text.split_inclusive('\n').collect::<Vec<_>>();
+    let numbered = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, number(line.trim_end())?)))
+        .collect::<Vec<_>>();
+
+    if numbered.iter().any(|&(_, number)| number == key) {
+        return Err(format!("`{}` is already there", entry.trim()).into());
+    }
+
+    let index = match numbered.iter().find(|&&(_, number)| number > key) {
+        Some(&(index, _)) => index,
+        None => match numbered.last() {
+            Some(&(index, _)) => index + 1,
+            None => return Err(format!("found nowhere to add `{}`", entry.trim()).into()),
+        },
+    };
+
+    let entry = format!("{entry}{newline}");
+    let mut lines = lines;
+    lines.insert(index, &entry);
+
+    Ok(lines.concat())
+}
+
+/// Reads the file at `path` and returns it changed by `edit`, without writing it back.
+fn edited(path: &Path, edit: impl FnOnce(&str) -> Result<String>) -> Result<String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("could not read {}: {err}", path.display()))?;
+
+    edit(&text).map_err(|err| format!("could not edit {}: {err}", path.display()).into())
+}
+
+fn edit(path: &Path, edit: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let text = edited(path, edit)?;
+
+    fs::write(path, text).map_err(|err| format!("could not write {}: {err}", path.display()).into())
+}
+
+fn write_new(path: &Path, content: &str) -> Result<()> {
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+    }
+
+    fs::write(path, content).map_err(|err| format!("could not write {}: {err}", path.display()))?;
+
+    println!("created {}", path.display());
+
+    Ok(())
+}
+
+/// Adds `pub mod dayN;`, `&dayN::DayN` and the day's example tests to a year crate's `lib.rs`.
+fn register_day(lib: &str, day: u8) -> Result<String> {
+    let day = u16::from(day);
+
+    let lib = insert_numbered(lib, day, &format!("pub mod day{day};"), |line| {
+        line.strip_prefix("pub mod day")?
+            .strip_suffix(';')?
+            .parse()
+            .ok()
+    })?;
+
+    let lib = insert_numbered(&lib, day, &format!("    &day{day}::Day{day},"), |line| {
+        line.trim()
+            .strip_prefix("&day")?
+            .split_once("::")?
+            .0
+            .parse()
+            .ok()
+    })?;
+
+    insert_numbered(&lib, day, &format!("    day{day}::Day{day},"), |line| {
+        line.trim()
+            .strip_prefix("day")?
+            .split_once("::")?
+            .0
+            .parse()
+            .ok()
+    })
+}
+
+/// Adds the crate of `year` to the members of the workspace manifest.
+fn add_member(manifest: &str, year: u16) -> Result<String> {
+    insert_numbered(manifest, year, &format!("    \"aoc_{year}\","), |line| {
+        line.trim()
+            .strip_prefix("\"aoc_")?
+            .strip_suffix("\",")?
+            .parse()
+            .ok()
+    })
+}
+
+/// Adds the crate of `year` to the dependencies of the runner.
+fn add_dependency(manifest: &str, year: u16) -> Result<String> {
+    let entry = format!("aoc_{year} = {{ path = \"../aoc_{year}\" }}");
+
+    insert_numbered(manifest, year, &entry, |line| {
+        line.strip_prefix("aoc_")?.split_once(' ')?.0.parse().ok()
+    })
+}
+
+/// Adds the crate of `year` to the runner's registry of years.
+fn add_year(solutions: &str, year: u16) -> Result<String> {
+    let start = solutions
+        .find("pub static YEARS")
+        .ok_or("found no `YEARS` registry")?;
+    let end = solutions[start..]
+        .find("\n];")
+        .map(|end| start + end + 1)
+        .ok_or("found no end to the `YEARS` registry")?;
+
+    // Keeps the years in order, before the first entry for a later year.
+    let index = solutions[start..end]
+        .match_indices("    Year {")
+        .map(|(index, _)| start + index)
+        .find(|&index| {
+            let entry = &solutions[index..];
+            let entry_year = entry
+                .split_once("year: ")
+                .and_then(|(_, rest)| rest.split_once(','))
+                .and_then(|(entry_year, _)| entry_year.parse::<u16>().ok());
+
+            entry_year.is_some_and(|entry_year| entry_year > year)
+        })
+        .unwrap_or(end);
+
+    let entry = format!(
+        "    Year {{\n        year: {year},\n        solutions: aoc_{year}::SOLUTIONS,\n        inputs: aoc_{year}::inputs,\n    }},\n"
+    );
+
+    Ok(format!(
+        "{}{entry}{}",
+        &solutions[..index],
+        &solutions[index..]
+    ))
+}
+
+/// Creates the crate of a year that has none yet, with `day` as its only day, and hooks it up to
+/// the workspace and the runner.
+fn new_year(workspace: &Path, year: u16, day: u8, title: &str) -> Result<()> {
+    let dir = workspace.join(format!("aoc_{year}"));
+
+    write_new(
+        &dir.join("Cargo.toml"),
+        &render(MANIFEST_TEMPLATE, year, day, title),
+    )?;
+    write_new(
+        &dir.join("src/lib.rs"),
+        &render(LIB_TEMPLATE, year, day, title),
+    )?;
+
+    fs::create_dir_all(dir.join("src/input"))
+        .map_err(|err| format!("could not create the input directory of {year}: {err}"))?;
+
+    edit(&workspace.join("Cargo.toml"), |text| add_member(text, year))?;
+    edit(&workspace.join("aoc/Cargo.toml"), |text| {
+        add_dependency(text, year)
+    })?;
+    edit(&workspace.join("aoc/src/solutions.rs"), |text| {
+        add_year(text, year)
+    })?;
+
+    println!("added aoc_{year} to the workspace and the runner");
+
+    Ok(())
+}
+
+pub fn new(options: Options) -> Result<()> {
+    let Options { year, day, .. } = options;
+
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {day}, days go from 1 to 25").into());
+    }
+
+    let workspace = match options.workspace {
+        Some(workspace) => workspace,
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .ok_or("could not find the workspace")?
+            .to_path_buf(),
+    };
+    let title = options.title.unwrap_or_else(|| format!("Day {day}"));
+    let src = workspace.join(format!("aoc_{year}/src"));
+    let path = src.join(format!("day{day}.rs"));
+    let example = src.join(format!("examples/day{day}.toml"));
+    let lib_path = src.join("lib.rs");
+
+    for path in [&path, &example] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()).into());
+        }
+    }
+
+    // `lib.rs` is only changed once the day's files are written, so a failure never leaves it
+    // declaring a module that has no file.
+    let lib = match src.exists() {
+        true => Some(edited(&lib_path, |text| register_day(text, day))?),
+        false => None,
+    };
+
+    write_new(&example, &render(EXAMPLE_TEMPLATE, year, day, &title))?;
+
+    if let Err(err) = write_new(&path, &render(DAY_TEMPLATE, year, day, &title)) {
+        let _ = fs::remove_file(&example);
+
+        return Err(err);
+    }
+
+    match lib {
+        Some(lib) => fs::write(&lib_path, lib)
+            .map_err(|err| format!("could not write {}: {err}", lib_path.display()).into()),
+        None => new_year(&workspace, year, day, &title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    const LIB: &str = "use aoc_common::{Inputs, Puzzle};
+
+pub mod day1;
+pub mod day3;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day3::Day3,
+];
+
+aoc_common::example_tests! {
+    day1::Day1,
+    day3::Day3,
+}
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        let lib = register_day(LIB, 2).unwrap();
+
+        assert!(lib.contains("pub mod day1;\npub mod day2;\npub mod day3;\n"));
+        assert!(lib.contains("    &day1::Day1,\n    &day2::Day2,\n    &day3::Day3,\n"));
+        assert!(lib.contains("    day1::Day1,\n    day2::Day2,\n    day3::Day3,\n"));
+
+        let lib = register_day(&lib, 10).unwrap();
+
+        assert!(lib.contains("pub mod day3;\npub mod day10;\n\n"));
+        assert!(lib.contains("    &day3::Day3,\n    &day10::Day10,\n];"));
+        assert_eq!(
+            "`pub mod day3;` is already there",
+            register_day(LIB, 3).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        let lib = register_day(&LIB.replace('\n', "\r\n"), 2).unwrap();
+
+        assert!(lib.contains("pub mod day1;\r\npub mod day2;\r\n"));
+        assert!(!lib.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn adds_years_to_manifests() {
+        let manifest = "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc_common\",\n    \"aoc_2015\",\n    \"aoc_2024\",\n]\n";
+
+        assert_eq!(
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc_common\",\n    \"aoc_2015\",\n    \"aoc_2023\",\n    \"aoc_2024\",\n]\n",
+            add_member(manifest, 2023).unwrap()
+        );
+        assert_eq!(
+            "[dependencies]\naoc_2015 = { path = \"../aoc_2015\" }\naoc_2016 = { path = \"../aoc_2016\" }\ntoml = \"0.8\"\n",
+            add_dependency(
+                "[dependencies]\naoc_2015 = { path = \"../aoc_2015\" }\ntoml = \"0.8\"\n",
+                2016
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn renders_escaped_titles() {
+        let day = render(DAY_TEMPLATE, 2023, 7, "Say \"Hi\"");
+
+        assert!(day.contains("pub struct Day7;"));
+        assert!(day.contains("const YEAR: u16 = 2023;"));
+        assert!(day.contains("const TITLE: &'static str = \"Say \\\"Hi\\\"\";"));
+        assert!(day.contains("crate::inputs().load(7)"));
+    }
+
+    #[test]
+    fn creates_new_years() {
+        let workspace = env::temp_dir().join(format!("aoc_scaffold_{}", process::id()));
+        let _ = fs::remove_dir_all(&workspace);
+        fs::create_dir_all(workspace.join("aoc/src")).unwrap();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc_2022\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("aoc/Cargo.toml"),
+            "[dependencies]\naoc_2022 = { path = \"../aoc_2022\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("aoc/src/solutions.rs"),
+            include_str!("solutions.rs"),
+        )
+        .unwrap();
+
+        let options = |day| Options {
+            year: 2023,
+            day,
+            title: Some("Trebuchet?!".to_string()),
+            workspace: Some(workspace.clone()),
+        };
+
+        new(options(1)).unwrap();
+        new(options(2)).unwrap();
+
+        let read = |path: &str| fs::read_to_string(workspace.join(path)).unwrap();
+
+        assert!(read("aoc_2023/src/lib.rs").contains("pub mod day1;\npub mod day2;\n"));
+        assert!(read("aoc_2023/src/day2.rs").contains("const DAY: u8 = 2;"));
+        assert!(read("aoc_2023/src/examples/day2.toml").contains("[example]"));
+        assert!(read("aoc_2023/Cargo.toml").contains("name = \"aoc_2023\""));
+        assert!(workspace.join("aoc_2023/src/input").is_dir());
+        assert!(read("Cargo.toml").contains("\"aoc_2022\",\n    \"aoc_2023\",\n]"));
+        assert!(read("aoc/Cargo.toml").contains("aoc_2023 = { path = \"../aoc_2023\" }"));
+        assert!(read("aoc/src/solutions.rs").contains(
+            "        year: 2023,\n        solutions: aoc_2023::SOLUTIONS,\n        inputs: aoc_2023::inputs,\n    },\n    Year {\n        year: 2024,"
+        ));
+        assert_eq!(
+            format!(
+                "{} already exists",
+                workspace.join("aoc_2023/src/day2.rs").display()
+            ),
+            new(options(2)).unwrap_err().to_string()
+        );
+
+        // A leftover example stops the day before anything is written, `lib.rs` included.
+        fs::write(workspace.join("aoc_2023/src/examples/day3.toml"), "").unwrap();
+
+        assert_eq!(
+            format!(
+                "{} already exists",
+                workspace.join("aoc_2023/src/examples/day3.toml").display()
+            ),
+            new(options(3)).unwrap_err().to_string()
+        );
+        assert!(!read("aoc_2023/src/lib.rs").contains("day3"));
+        assert!(!workspace.join("aoc_2023/src/day3.rs").exists());
+
+        fs::remove_dir_all(workspace).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+use aoc_common::{Inputs, Puzzle};
+
+pub struct Year {
+    pub year: u16,
+    pub solutions: &'static [&'static dyn Puzzle],
+    pub inputs: fn() -> Inputs,
+}
+
+pub static YEARS: &[Year] = &[
+    Year {
+        year: 2015,
+        solutions: aoc_2015::SOLUTIONS,
+        inputs: aoc_2015::inputs,
+    },
+    Year {
+        year: 2022,
+        solutions: aoc_2022::SOLUTIONS,
+        inputs: aoc_2022::inputs,
+    },
+    Year {
+        year: 2024,
+        solutions: aoc_2024::SOLUTIONS,
+        inputs: aoc_2024::inputs,
+    },
+];
+
+pub fn year(year: u16) -> Option<&'static Year> {
+    YEARS.iter().find(|entry| entry.year == year)
+}
+
+pub fn all() -> impl Iterator<Item = &'static dyn Puzzle> {
+    YEARS.iter().flat_map(|year| year.solutions).copied()
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static dyn Puzzle> {
+    all().find(|puzzle| puzzle.year() == year && puzzle.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{Answer, Part};
+
+    use super::*;
+
+    #[test]
+    fn registries_are_unique() {
+        let mut days = all()
+            .map(|puzzle| (puzzle.year(), puzzle.day()))
+            .collect::<Vec<_>>();
+        let count = days.len();
+
+        days.sort();
+        days.dedup();
+
+        assert_eq!(count, days.len());
+    }
+
+    #[test]
+    fn registries_match_their_year() {
+        for year in YEARS {
+            assert!(year
+                .solutions
+                .iter()
+                .all(|puzzle| puzzle.year() == year.year));
+        }
+    }
+
+    #[test]
+    fn find_known_day() {
+        let puzzle = find(2022, 7).unwrap();
+
+        assert_eq!("No Space Left On Device", puzzle.title());
+    }
+
+    #[test]
+    fn find_unknown_day() {
+        assert!(find(2022, 25).is_none());
+        assert!(find(2016, 1).is_none());
+    }
+
+    #[test]
+    fn solve_example() {
+        let puzzle = find(2015, 1).unwrap();
+
+        assert_eq!(Ok(Answer::Number(3)), puzzle.solve(Part::One, "(((", &[]));
+        assert_eq!(Ok(Answer::Unsolved), puzzle.solve(Part::Two, "(((", &[]));
+    }
+
+    #[test]
+    fn solve_reports_invalid_input() {
+        let puzzle = find(2015, 1).unwrap();
+
+        assert_eq!(
+            "invalid input: expected `(` or `)` at line 1, column 3: `x`",
+            puzzle.solve(Part::One, "((x", &[]).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn solve_example_with_params() {
+        let puzzle = find(2022, 5).unwrap();
+        let instructions = "move 1 from 2 to 1
+        move 3 from 1 to 3
+        move 2 from 2 to 1
+        move 1 from 1 to 2";
+        let stacks = [("stacks".to_string(), "NZ,DCM,P".to_string())];
+
+        assert_eq!(
+            Ok(Answer::Text("CMZ".to_string())),
+            puzzle.solve(Part::One, instructions, &stacks)
+        );
+    }
+}
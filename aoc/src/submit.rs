@@ -0,0 +1,391 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use aoc_common::{Answer, Part};
+use clap::Args;
+
+use crate::{
+    client::{self, Client},
+    fetch,
+    ledger::{Ledger, Rejection},
+    solutions, Result,
+};
+
+#[derive(Args)]
+pub struct Options {
+    year: u16,
+    day: u8,
+    part: Part,
+    /// The answer to send, instead of solving the part against the saved input
+    answer: Option<String>,
+    /// Read the input and keep the ledger in this directory instead of the year's input directory
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+    #[command(flatten)]
+    client: client::Options,
+}
+
+/// What the website made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The answer was wrong, and the website asked to wait before sending another one.
+    Rejected(Rejection, Option<Duration>),
+    /// An answer was sent too recently, so this one was not looked at.
+    Wait(Duration),
+    /// The part is already solved, or not unlocked yet.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page the website answers a submission with.
+    pub fn parse(page: &str) -> Option<Self> {
+        let message = message(page);
+        let lower = message.to_lowercase();
+
+        if lower.contains("that's the right answer") {
+            Some(Verdict::Correct)
+        } else if lower.contains("that's not the right answer") {
+            let rejection = if lower.contains("too high") {
+                Rejection::TooHigh
+            } else if lower.contains("too low") {
+                Rejection::TooLow
+            } else {
+                Rejection::Wrong
+            };
+
+            Some(Verdict::Rejected(rejection, retry_delay(&lower)))
+        } else if lower.contains("you gave an answer too recently") {
+            Some(Verdict::Wait(
+                time_left(&lower).unwrap_or(Duration::from_secs(60)),
+            ))
+        } else if lower.contains("you don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+/// The text of the `<article>` holding the website's reply, without markup.
+fn message(page: &str) -> String {
+    let article = match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &page[start..end],
+        _ => page,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for char in article.chars() {
+        match char {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            char if !in_tag => text.push(char),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads `you have 1m 20s left to wait`.
+fn time_left(message: &str) -> Option<Duration> {
+    let (_, rest) = message.rsplit_once("you have ")?;
+    let (left, _) = rest.split_once(" left")?;
+
+    left.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|char: char| !char.is_ascii_digit())?);
+            let number = number.parse::<u64>().ok()?;
+
+            match unit {
+                "s" => Some(number),
+                "m" => Some(number * 60),
+                "h" => Some(number * 60 * 60),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Reads `please wait one minute before trying again` or `wait 5 minutes before trying again`.
+fn retry_delay(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("wait ")?;
+    let mut words = rest.split_whitespace();
+
+    let number = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        number => number.parse::<u64>().ok()?,
+    };
+
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "second" | "seconds" => Some(Duration::from_secs(number)),
+        "minute" | "minutes" => Some(Duration::from_secs(number * 60)),
+        _ => None,
+    }
+}
+
+/// Sends `answer` unless the ledger already knows how the website will take it, and records the
+/// verdict in the ledger.
+pub fn send(
+    client: &Client,
+    ledger: &mut Ledger,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &str,
+) -> Result<Verdict> {
+    let answer = answer.trim();
+
+    if let Some(accepted) = ledger.get(day, part) {
+        return match accepted == answer {
+            true => Ok(Verdict::AlreadySolved),
+            false => Err(format!(
+                "{year} day {day} part {part} was already solved with {accepted}"
+            )
+            .into()),
+        };
+    }
+
+    if let Some(rejection) = ledger.rejection(day, part, answer) {
+        return Err(format!(
+            "{answer} is known to be {}, not sending it again",
+            rejection.label()
+        )
+        .into());
+    }
+
+    if let Some(time) = ledger.next_submission() {
+        let wait = time.duration_since(SystemTime::now()).unwrap_or_default();
+
+        return Err(format!(
+            "the website asked to wait {}s before sending another answer",
+            wait.as_secs().max(1)
+        )
+        .into());
+    }
+
+    let page = client
+        .post_form(
+            &format!("/{year}/day/{day}/answer"),
+            &[("level", &part.to_string()), ("answer", answer)],
+        )
+        .map_err(|err| format!("could not submit {year} day {day} part {part}: {err}"))?;
+
+    let verdict = Verdict::parse(&page).ok_or_else(|| {
+        format!(
+            "could not make sense of the answer to {year} day {day} part {part}: {}",
+            message(&page)
+        )
+    })?;
+
+    match &verdict {
+        Verdict::Correct => ledger.set(day, part, answer),
+        Verdict::Rejected(rejection, wait) => {
+            ledger.reject(day, part, answer, *rejection);
+
+            if let Some(wait) = wait {
+                ledger.set_next_submission(SystemTime::now() + *wait);
+            }
+        }
+        Verdict::Wait(wait) => ledger.set_next_submission(SystemTime::now() + *wait),
+        Verdict::AlreadySolved => {}
+    }
+
+    Ok(verdict)
+}
+
+pub fn submit(options: Options) -> Result<()> {
+    let Options {
+        year, day, part, ..
+    } = options;
+
+    let inputs = fetch::inputs(year, options.input_dir.as_deref())?;
+
+    let answer = match options.answer {
+        Some(answer) => answer,
+        None => {
+            let puzzle = solutions::find(year, day).ok_or_else(|| {
+                format!("{year} day {day} has not been solved yet, pass the answer to send")
+            })?;
+            let input = inputs.load(day)?;
+
+            match puzzle
+                .solve(part, &input, &[])
+                .map_err(|err| format!("{year} day {day}: {err}"))?
+            {
+                Answer::Unsolved => {
+                    return Err(format!(
+                        "{year} day {day} part {part} found no answer for this input"
+                    )
+                    .into())
+                }
+                answer => answer.to_string(),
+            }
+        }
+    };
+
+    let mut ledger = Ledger::load(&inputs)?;
+    let verdict = send(
+        &options.client.client()?,
+        &mut ledger,
+        year,
+        day,
+        part,
+        &answer,
+    );
+
+    // Saved even when sending failed, since the ledger may have learnt something before that.
+    ledger.save(&inputs)?;
+
+    match verdict? {
+        Verdict::Correct => println!("{answer} is the right answer, recorded in the ledger"),
+        Verdict::Rejected(rejection, _) => {
+            return Err(format!("{answer} is {}, recorded in the ledger", rejection.label()).into())
+        }
+        Verdict::Wait(wait) => {
+            return Err(format!(
+                "an answer was sent too recently, wait {}s before trying again",
+                wait.as_secs()
+            )
+            .into())
+        }
+        Verdict::AlreadySolved => println!("{year} day {day} part {part} is already solved"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock_server::MockServer;
+
+    use super::*;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(
+            Some(Verdict::Correct),
+            Verdict::parse(&page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."))
+        );
+        assert_eq!(
+            Some(Verdict::Rejected(Rejection::TooHigh, Some(Duration::from_secs(60)))),
+            Verdict::parse(&page("That's not the right answer; your answer is too high.  Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a>"))
+        );
+        assert_eq!(
+            Some(Verdict::Rejected(Rejection::TooLow, Some(Duration::from_secs(300)))),
+            Verdict::parse(&page("That's not the right answer; your answer is too low.  Please wait 5 minutes before trying again."))
+        );
+        assert_eq!(
+            Some(Verdict::Rejected(Rejection::Wrong, None)),
+            Verdict::parse(&page("That's not the right answer.  If you're stuck, make sure you're using the full input data."))
+        );
+        assert_eq!(
+            Some(Verdict::Wait(Duration::from_secs(80))),
+            Verdict::parse(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 20s left to wait."))
+        );
+        assert_eq!(
+            Some(Verdict::AlreadySolved),
+            Verdict::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+        );
+        assert_eq!(
+            None,
+            Verdict::parse("<html>Puzzle inputs differ by user.</html>")
+        );
+    }
+
+    #[test]
+    fn records_accepted_answers() {
+        let server = MockServer::start(vec![(200, page("That's the right answer!"))]);
+        let client = Client::new(&server.url(), "abc123");
+        let mut ledger = Ledger::default();
+
+        let verdict = send(&client, &mut ledger, 2022, 1, Part::Two, "45000").unwrap();
+
+        assert_eq!(Verdict::Correct, verdict);
+        assert_eq!(Some("45000".to_string()), ledger.get(1, Part::Two));
+
+        let requests = server.requests();
+
+        assert_eq!("/2022/day/1/answer", requests[0].path);
+        assert_eq!("level=2&answer=45000", requests[0].body);
+    }
+
+    #[test]
+    fn never_resends_rejected_answers() {
+        let server = MockServer::start(vec![(
+            200,
+            page("That's not the right answer; your answer is too high."),
+        )]);
+        let client = Client::new(&server.url(), "abc123");
+        let mut ledger = Ledger::default();
+
+        let verdict = send(&client, &mut ledger, 2022, 1, Part::One, "70000").unwrap();
+
+        assert_eq!(Verdict::Rejected(Rejection::TooHigh, None), verdict);
+
+        for answer in ["70000", "71000"] {
+            assert_eq!(
+                format!("{answer} is known to be too high, not sending it again"),
+                send(&client, &mut ledger, 2022, 1, Part::One, answer)
+                    .unwrap_err()
+                    .to_string()
+            );
+        }
+
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn waits_when_asked_to() {
+        let server = MockServer::start(vec![(
+            200,
+            page("You gave an answer too recently. You have 34s left to wait."),
+        )]);
+        let client = Client::new(&server.url(), "abc123");
+        let mut ledger = Ledger::default();
+
+        let verdict = send(&client, &mut ledger, 2022, 2, Part::One, "15").unwrap();
+
+        assert_eq!(Verdict::Wait(Duration::from_secs(34)), verdict);
+        assert!(send(&client, &mut ledger, 2022, 2, Part::One, "15")
+            .unwrap_err()
+            .to_string()
+            .starts_with("the website asked to wait"));
+        assert_eq!(None, ledger.get(2, Part::One));
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn never_resends_solved_parts() {
+        let server = MockServer::start(vec![]);
+        let client = Client::new(&server.url(), "abc123");
+        let mut ledger = Ledger::default();
+        ledger.set(7, Part::One, "95437");
+
+        assert_eq!(
+            Verdict::AlreadySolved,
+            send(&client, &mut ledger, 2022, 7, Part::One, "95437").unwrap()
+        );
+        assert_eq!(
+            "2022 day 7 part 1 was already solved with 95437",
+            send(&client, &mut ledger, 2022, 7, Part::One, "1")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(server.requests().is_empty());
+    }
+}
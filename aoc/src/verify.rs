@@ -0,0 +1,185 @@
+use aoc_common::{Answer, Part};
+
+use crate::{ledger::Ledger, solutions, Result};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// No answer has been recorded in the ledger yet.
+    Unknown,
+    NoInput,
+    /// The solution could not run on the input, such as when it does not parse.
+    Error,
+}
+
+impl Status {
+    fn check(expected: Option<&str>, answer: &Answer) -> Self {
+        match (expected, answer) {
+            (None, _) => Status::Unknown,
+            (Some(_), Answer::Unsolved) => Status::Fail,
+            (Some(expected), answer) => match expected.trim() == answer.to_string().trim() {
+                true => Status::Pass,
+                false => Status::Fail,
+            },
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Unknown => "unknown",
+            Status::NoInput => "no input",
+            Status::Error => "ERROR",
+        }
+    }
+}
+
+struct Row {
+    year: u16,
+    day: u8,
+    part: Option<Part>,
+    status: Status,
+    answer: String,
+    expected: String,
+}
+
+/// Shortens multi-line answers, such as rendered letters, to fit on a single row.
+fn summarise(answer: &str) -> String {
+    let mut lines = answer.trim().lines();
+    let first = lines.next().unwrap_or_default();
+
+    match lines.next() {
+        Some(_) => format!("{first}..."),
+        None => first.to_string(),
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let answer_width = rows
+        .iter()
+        .map(|row| row.answer.chars().count())
+        .chain([6])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:<4}  {:>3}  {:>4}  {:<8}  {:<answer_width$}  expected",
+        "year", "day", "part", "status", "answer"
+    );
+
+    for row in rows {
+        let part = row.part.map_or("-".to_string(), |part| part.to_string());
+
+        let line = format!(
+            "{:<4}  {:>3}  {:>4}  {:<8}  {:<answer_width$}  {}",
+            row.year,
+            row.day,
+            part,
+            row.status.label(),
+            row.answer,
+            row.expected
+        );
+
+        println!("{}", line.trim_end());
+    }
+}
+
+pub fn verify(year: Option<u16>, day: Option<u8>) -> Result<()> {
+    let mut rows = vec![];
+
+    let years = solutions::YEARS
+        .iter()
+        .filter(|entry| year.is_none_or(|year| year == entry.year));
+
+    for entry in years {
+        let inputs = (entry.inputs)();
+        let ledger = Ledger::load(&inputs)?;
+
+        let puzzles = entry
+            .solutions
+            .iter()
+            .filter(|puzzle| day.is_none_or(|day| day == puzzle.day()));
+
+        for puzzle in puzzles {
+            let Ok(input) = inputs.load(puzzle.day()) else {
+                rows.push(Row {
+                    year: entry.year,
+                    day: puzzle.day(),
+                    part: None,
+                    status: Status::NoInput,
+                    answer: String::new(),
+                    expected: String::new(),
+                });
+
+                continue;
+            };
+
+            for part in Part::ALL {
+                let expected = ledger.get(puzzle.day(), part);
+                let (status, answer) = match puzzle.solve(part, &input, &[]) {
+                    Ok(answer) => (
+                        Status::check(expected.as_deref(), &answer),
+                        answer.to_string(),
+                    ),
+                    Err(err) => (Status::Error, err.to_string()),
+                };
+
+                rows.push(Row {
+                    year: entry.year,
+                    day: puzzle.day(),
+                    part: Some(part),
+                    status,
+                    answer: summarise(&answer),
+                    expected: expected.as_deref().map(summarise).unwrap_or_default(),
+                });
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        return Err("no solutions match the given year and day".into());
+    }
+
+    print_table(&rows);
+
+    let failures = rows
+        .iter()
+        .filter(|row| matches!(row.status, Status::Fail | Status::Error))
+        .count();
+
+    match failures {
+        0 => Ok(()),
+        1 => Err("1 answer did not match the ledger or could not be solved".into()),
+        failures => Err(format!(
+            "{failures} answers did not match the ledger or could not be solved"
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_against_ledger() {
+        assert_eq!(
+            Status::Pass,
+            Status::check(Some("1543140"), &Answer::Number(1543140))
+        );
+        assert_eq!(
+            Status::Fail,
+            Status::check(Some("1543140"), &Answer::Number(95437))
+        );
+        assert_eq!(Status::Fail, Status::check(Some("1795"), &Answer::Unsolved));
+        assert_eq!(Status::Unknown, Status::check(None, &Answer::Number(74)));
+    }
+
+    #[test]
+    fn summarise_multi_line_answers() {
+        assert_eq!("RTGWZTHLD", summarise("RTGWZTHLD"));
+        assert_eq!("████ ████...", summarise("████ ████\n█       █"));
+    }
+}
@@ -1,90 +1,118 @@
-struct Floors<'a> {
-    instructions: std::slice::Iter<'a, u8>,
-    floor: i32,
-}
-
-impl<'a> Iterator for Floors<'a> {
-    type Item = i32;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.instructions.next().map(|floor| {
-            match floor {
-                b'(' => self.floor += 1,
-                b')' => self.floor -= 1,
-                _ => unreachable!(),
-            }
-
-            self.floor
-        })
-    }
-}
-
-impl<'a> From<&'a str> for Floors<'a> {
-    fn from(input: &'a str) -> Self {
-        Self {
-            instructions: input.trim().as_bytes().iter(),
-            floor: 0,
-        }
-    }
-}
-
-fn solution_1(input: &str) -> i32 {
-    let floors = Floors::from(input);
-
-    let result = floors.collect::<Vec<_>>();
-
-    result.into_iter().last().unwrap()
-}
-
-fn solution_2(input: &str) -> Option<usize> {
-    let floors = Floors::from(input);
-
-    let result = floors.collect::<Vec<_>>();
-
-    result.into_iter().position(|x| x < 0).map(|x| x + 1)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        assert_eq!(0, solution_1("(())"));
-        assert_eq!(0, solution_1("()()"));
-        assert_eq!(3, solution_1("((("));
-        assert_eq!(3, solution_1("(()(()("));
-        assert_eq!(3, solution_1("))((((("));
-        assert_eq!(-1, solution_1("())"));
-        assert_eq!(-1, solution_1("))("));
-        assert_eq!(-3, solution_1(")))"));
-        assert_eq!(-3, solution_1(")())())"));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day1.txt");
-
-        assert_eq!(74, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        assert_eq!(None, solution_2("(())"));
-        assert_eq!(None, solution_2("()()"));
-        assert_eq!(None, solution_2("((("));
-        assert_eq!(None, solution_2("(()(()("));
-        assert_eq!(Some(1), solution_2("))((((("));
-        assert_eq!(Some(3), solution_2("())"));
-        assert_eq!(Some(1), solution_2("))("));
-        assert_eq!(Some(1), solution_2(")))"));
-        assert_eq!(Some(1), solution_2(")())())"));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day1.txt");
-
-        assert_eq!(Some(1795), solution_2(input));
-    }
-}
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+
+struct Floors<'a> {
+    instructions: std::slice::Iter<'a, i32>,
+    floor: i32,
+}
+
+impl<'a> Iterator for Floors<'a> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.instructions.next().map(|step| {
+            self.floor += step;
+
+            self.floor
+        })
+    }
+}
+
+impl<'a> From<&'a [i32]> for Floors<'a> {
+    fn from(instructions: &'a [i32]) -> Self {
+        Self {
+            instructions: instructions.iter(),
+            floor: 0,
+        }
+    }
+}
+
+/// Turns each `(` into a step up and each `)` into a step down.
+pub fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+    let instructions = input.trim();
+
+    instructions
+        .char_indices()
+        .map(|(index, char)| match char {
+            '(' => Ok(1),
+            ')' => Ok(-1),
+            _ => Err(ParseError::at(
+                input,
+                &instructions[index..index + char.len_utf8()],
+                "expected `(` or `)`",
+            )),
+        })
+        .collect()
+}
+
+/// The floor the instructions end on, which is the ground floor when there are none.
+pub fn solution_1(input: &[i32]) -> i32 {
+    let floors = Floors::from(input);
+
+    floors.last().unwrap_or(0)
+}
+
+pub fn solution_2(input: &[i32]) -> Option<usize> {
+    let floors = Floors::from(input);
+
+    let result = floors.collect::<Vec<_>>();
+
+    result.into_iter().position(|x| x < 0).map(|x| x + 1)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Not Quite Lisp";
+
+    type Params = ();
+    type Input<'a> = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(1));
+
+        assert_eq!(74, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_other_characters() {
+        assert_eq!(
+            Err(ParseError::new(1, 4, "x", "expected `(` or `)`")),
+            parse(" ((x)")
+        );
+    }
+
+    #[test]
+    fn stay_on_the_ground_floor_without_instructions() {
+        assert_eq!(0, solution_1(&parse("\n").unwrap()));
+        assert_eq!(None, solution_2(&parse("").unwrap()));
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(1));
+
+        assert_eq!(Some(1795), solution_2(&parse(&input).unwrap()));
+    }
+}
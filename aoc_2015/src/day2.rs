@@ -1,97 +1,140 @@
-use itertools::Itertools;
-
-struct Rectangle {
-    length: u32,
-    width: u32,
-    height: u32,
-}
-
-impl Rectangle {
-    fn get_dimensions_windows(&self) -> impl Iterator<Item = (u32, u32)> {
-        [self.length, self.width, self.height]
-            .into_iter()
-            .circular_tuple_windows()
-    }
-
-    fn get_paper_dimensions(&self) -> u32 {
-        self.get_dimensions_windows()
-            .map(|(l, r)| 2 * l * r)
-            .sum::<u32>()
-            + self.get_smallest_area()
-    }
-
-    fn get_smallest_area(&self) -> u32 {
-        self.get_dimensions_windows()
-            .map(|(l, r)| l * r)
-            .min()
-            .unwrap()
-    }
-
-    fn get_ribbon_length(&self) -> u32 {
-        let mut smallest = [self.length, self.width, self.height];
-        smallest.sort();
-
-        let first = smallest.get(0).unwrap();
-        let second = smallest.get(1).unwrap();
-
-        let mut initial = first + first + second + second;
-
-        initial += self.length * self.width * self.height;
-
-        initial
-    }
-
-    fn build(input: &str) -> Self {
-        let mut split = input.trim().split('x');
-        let length = split.next().unwrap().parse::<u32>().unwrap();
-        let width = split.next().unwrap().parse::<u32>().unwrap();
-        let height = split.next().unwrap().parse::<u32>().unwrap();
-
-        Self {
-            length,
-            width,
-            height,
-        }
-    }
-}
-
-fn solution_1(input: &str) -> u32 {
-    let rectangles = input.trim().lines().map(|line| Rectangle::build(line));
-
-    rectangles.fold(0, |curr, accum| curr + accum.get_paper_dimensions())
-}
-
-fn solution_2(input: &str) -> u32 {
-    let rectangles = input.trim().lines().map(|line| Rectangle::build(line));
-
-    rectangles.fold(0, |curr, accum| curr + accum.get_ribbon_length())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        assert_eq!(58, solution_1("2x3x4"));
-        assert_eq!(43, solution_1("1x1x10"));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day2.txt");
-        assert_eq!(1588178, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        assert_eq!(34, solution_2("2x3x4"));
-        assert_eq!(14, solution_2("1x1x10"));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day2.txt");
-        assert_eq!(3783758, solution_2(input));
-    }
-}
+use aoc_common::{parse::parse_number, Answer, ParseError, Solution, SolveError};
+use itertools::Itertools;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rectangle {
+    length: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    fn get_dimensions_windows(&self) -> impl Iterator<Item = (u32, u32)> {
+        [self.length, self.width, self.height]
+            .into_iter()
+            .circular_tuple_windows()
+    }
+
+    fn get_paper_dimensions(&self) -> u32 {
+        self.get_dimensions_windows()
+            .map(|(l, r)| 2 * l * r)
+            .sum::<u32>()
+            + self.get_smallest_area()
+    }
+
+    fn get_smallest_area(&self) -> u32 {
+        self.get_dimensions_windows()
+            .map(|(l, r)| l * r)
+            .min()
+            .unwrap()
+    }
+
+    fn get_ribbon_length(&self) -> u32 {
+        let mut smallest = [self.length, self.width, self.height];
+        smallest.sort();
+
+        let first = smallest.first().unwrap();
+        let second = smallest.get(1).unwrap();
+
+        let mut initial = first + first + second + second;
+
+        initial += self.length * self.width * self.height;
+
+        initial
+    }
+
+    /// Builds a rectangle from `line`, a line of `input` such as `2x3x4`.
+    fn build(input: &str, line: &str) -> Result<Self, ParseError> {
+        let line = line.trim();
+        let mut split = line.split('x');
+        let mut dimension = || match split.next() {
+            Some(text) => parse_number::<u32>(input, text),
+            None => Err(ParseError::at(input, line, "expected three dimensions")),
+        };
+
+        let rectangle = Self {
+            length: dimension()?,
+            width: dimension()?,
+            height: dimension()?,
+        };
+
+        match split.next() {
+            Some(text) => Err(ParseError::at(input, text, "expected three dimensions")),
+            None => Ok(rectangle),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Rectangle>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| Rectangle::build(input, line))
+        .collect()
+}
+
+pub fn solution_1(rectangles: &[Rectangle]) -> u32 {
+    rectangles
+        .iter()
+        .fold(0, |curr, accum| curr + accum.get_paper_dimensions())
+}
+
+pub fn solution_2(rectangles: &[Rectangle]) -> u32 {
+    rectangles
+        .iter()
+        .fold(0, |curr, accum| curr + accum.get_ribbon_length())
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "I Was Told There Would Be No Math";
+
+    type Params = ();
+    type Input<'a> = Vec<Rectangle>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(2));
+        assert_eq!(1588178, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_reports_bad_dimensions() {
+        assert_eq!(
+            Err(ParseError::new(2, 3, "y3", "expected a number")),
+            parse("2x3x4\n1xy3")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "2x3", "expected three dimensions")),
+            parse("2x3")
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(2));
+        assert_eq!(3783758, solution_2(&parse(&input).unwrap()));
+    }
+}
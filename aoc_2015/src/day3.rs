@@ -1,128 +1,112 @@
-use std::{collections::HashSet, ops::Add};
-
-#[derive(Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
-struct Position(i32, i32);
-
-impl From<char> for Direction {
-    fn from(input: char) -> Self {
-        match input {
-            '^' => Self::Up,
-            'v' => Self::Down,
-            '<' => Self::Left,
-            '>' => Self::Right,
-            _ => panic!("Invalid character specified"),
-        }
-    }
-}
-
-impl From<&Direction> for Position {
-    fn from(direction: &Direction) -> Self {
-        match direction {
-            Direction::Up => Position(0, 1),
-            Direction::Down => Position(0, -1),
-            Direction::Left => Position(-1, 0),
-            Direction::Right => Position(1, 0),
-        }
-    }
-}
-
-impl Add for Position {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-fn visit(instructions: &Vec<Direction>) -> Vec<Position> {
-    let mut visited = vec![Position(0, 0)];
-
-    for instruction in instructions {
-        let instruction = Position::from(instruction);
-        let last = visited.last().unwrap().clone();
-
-        visited.push(last + instruction);
-    }
-
-    visited
-}
-
-fn visit_duo(instructions: &Vec<Direction>) -> Vec<Position> {
-    let mut visited = vec![Position(0, 0)];
-
-    for direction in instructions {
-        let visited_length = visited.len();
-        let reference_pos = if visited_length < 3 {
-            Position(0, 0)
-        } else {
-            visited[visited_length - 2].clone()
-        };
-        
-        visited.push(reference_pos + Position::from(direction));
-    }
-
-    visited
-}
-
-fn solution_1(input: &str) -> usize {
-    let instructions = input
-        .trim()
-        .lines()
-        .flat_map(|line| line.chars().map(|char| Direction::from(char)))
-        .collect::<Vec<_>>();
-
-    let visited = visit(&instructions);
-
-    visited.into_iter().collect::<HashSet<Position>>().len()
-}
-
-fn solution_2(input: &str) -> usize {
-    let instructions = input
-        .trim()
-        .lines()
-        .flat_map(|line| line.chars().map(|char| Direction::from(char)))
-        .collect::<Vec<_>>();
-
-    let visited = visit_duo(&instructions);
-
-    visited.into_iter().collect::<HashSet<Position>>().len()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        assert_eq!(2, solution_1(">"));
-        assert_eq!(4, solution_1("^>v<"));
-        assert_eq!(2, solution_1("^v^v^v^v^v"));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day3.txt");
-        assert_eq!(2565, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        assert_eq!(3, solution_2("^v"));
-        assert_eq!(3, solution_2("^>v<"));
-        assert_eq!(11, solution_2("^v^v^v^v^v"));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day3.txt");
-        assert_eq!(2639, solution_2(input));
-    }
-}
+use std::collections::HashSet;
+
+use aoc_common::{Answer, Dir4, ParseError, Point2, Solution, SolveError};
+
+type Position = Point2<i32>;
+
+fn visit(instructions: &[Dir4]) -> Vec<Position> {
+    let mut visited = vec![Position::ORIGIN];
+
+    for instruction in instructions {
+        let last = *visited.last().unwrap();
+
+        visited.push(last + instruction.offset());
+    }
+
+    visited
+}
+
+fn visit_duo(instructions: &[Dir4]) -> Vec<Position> {
+    let mut visited = vec![Position::ORIGIN];
+
+    for direction in instructions {
+        let visited_length = visited.len();
+        let reference_pos = if visited_length < 3 {
+            Position::ORIGIN
+        } else {
+            visited[visited_length - 2]
+        };
+
+        visited.push(reference_pos + direction.offset());
+    }
+
+    visited
+}
+
+pub fn parse(input: &str) -> Result<Vec<Dir4>, ParseError> {
+    let directions = input.trim();
+
+    directions
+        .char_indices()
+        .filter(|(_, char)| !char.is_whitespace())
+        .map(|(index, char)| {
+            Dir4::try_from(char).map_err(|_| {
+                let text = &directions[index..index + char.len_utf8()];
+
+                ParseError::at(input, text, "expected one of `^>v<`")
+            })
+        })
+        .collect()
+}
+
+pub fn solution_1(instructions: &[Dir4]) -> usize {
+    let visited = visit(instructions);
+
+    visited.into_iter().collect::<HashSet<Position>>().len()
+}
+
+pub fn solution_2(instructions: &[Dir4]) -> usize {
+    let visited = visit_duo(instructions);
+
+    visited.into_iter().collect::<HashSet<Position>>().len()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Perfectly Spherical Houses in a Vacuum";
+
+    type Params = ();
+    type Input<'a> = Vec<Dir4>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(3));
+        assert_eq!(2565, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_directions() {
+        assert_eq!(
+            Err(ParseError::new(1, 3, "x", "expected one of `^>v<`")),
+            parse("^>x<")
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(3));
+        assert_eq!(2639, solution_2(&parse(&input).unwrap()));
+    }
+}
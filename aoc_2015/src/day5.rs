@@ -1,3 +1,4 @@
+use aoc_common::{Answer, ParseError, Solution, SolveError};
 use itertools::Itertools;
 
 const VOWELS: &[char] = &['a', 'e', 'i', 'o', 'u'];
@@ -23,7 +24,7 @@ fn old_matcher(input: &str) -> bool {
         && input
             .to_lowercase()
             .chars()
-            .filter(|char| VOWELS.contains(&char))
+            .filter(|char| VOWELS.contains(char))
             .count()
             >= 3
         && has_doubles
@@ -49,52 +50,56 @@ fn new_matcher(input: &str) -> bool {
     has_matching_pair && has_matching_separator
 }
 
-fn solution_1(input: &str) -> usize {
-    input
-        .trim()
-        .lines()
-        .filter(|line| old_matcher(line))
-        .count()
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    Ok(input.trim().lines().collect())
 }
 
-fn solution_2(input: &str) -> usize {
-    input
-        .trim()
-        .lines()
-        .filter(|line| new_matcher(line))
-        .count()
+pub fn solution_1(lines: &[&str]) -> usize {
+    lines.iter().filter(|line| old_matcher(line)).count()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn solution_2(lines: &[&str]) -> usize {
+    lines.iter().filter(|line| new_matcher(line)).count()
+}
 
-    #[test]
-    fn solution_1_example() {
-        assert_eq!(1, solution_1("ugknbfddgicrmopn"));
-        assert_eq!(1, solution_1("aaa"));
-        assert_eq!(0, solution_1("jchzalrnumimnmhp"));
-        assert_eq!(0, solution_1("haegwjzuvuyypxyu"));
-        assert_eq!(0, solution_1("dvszwmarrgswjxmb"));
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Doesn't He Have Intern-Elves For This?";
+
+    type Params = ();
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
     }
 
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day5.txt");
-        assert_eq!(238, solution_1(input));
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
     }
 
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
     #[test]
-    fn solution_2_example() {
-        assert_eq!(1, solution_2("qjhvhtzxzqqjkmpb"));
-        assert_eq!(1, solution_2("xxyxx"));
-        assert_eq!(0, solution_2("uurcxstgmygtbstg"));
-        assert_eq!(0, solution_2("ieodomkazucvgmuy"));
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(5));
+        assert_eq!(238, solution_1(&parse(&input).unwrap()));
     }
 
     #[test]
     fn solution_2_input() {
-        let input = include_str!("input/day5.txt");
-        assert_eq!(1, solution_2(input));
+        let input = input_or_skip!(crate::inputs().load(5));
+        assert_eq!(1, solution_2(&parse(&input).unwrap()));
     }
 }
@@ -1,32 +1,43 @@
-use std::collections::HashMap;
-
+use aoc_common::{parse::parse_all, Answer, Grid, ParseError, Point2, Solution, SolveError};
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::{complete::line_ending, streaming::char},
-    combinator::map,
+    combinator::{map, verify},
     multi::separated_list1,
     sequence::separated_pair,
     IResult,
 };
 
-enum Command {
+#[derive(Debug)]
+pub enum Command {
     TurnOn,
     TurnOff,
     Toggle,
 }
 
-fn parse_grid(input: &str) -> IResult<&str, (i32, i32)> {
-    separated_pair(
-        nom::character::complete::i32,
-        char(','),
-        nom::character::complete::i32,
+/// The lights form a square with this many lights on each side.
+const SIZE: usize = 1000;
+
+fn parse_grid(input: &str) -> IResult<&str, Point2<usize>> {
+    verify(
+        map(
+            separated_pair(
+                nom::character::complete::u32,
+                char(','),
+                nom::character::complete::u32,
+            ),
+            |(x, y)| Point2::new(x as usize, y as usize),
+        ),
+        |light| light.x < SIZE && light.y < SIZE,
     )(input)
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<(Command, ((i32, i32), (i32, i32)))>> {
-    let (_, result) = separated_list1(
+pub type Instruction = (Command, (Point2<usize>, Point2<usize>));
+
+fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
+    separated_list1(
         line_ending,
         separated_pair(
             alt((
@@ -37,25 +48,25 @@ fn parse(input: &str) -> IResult<&str, Vec<(Command, ((i32, i32), (i32, i32)))>>
             tag(" "),
             separated_pair(parse_grid, tag(" through "), parse_grid),
         ),
-    )(input)?;
-
-    Ok((input, result))
+    )(input)
 }
 
-fn solution_1(input: &str) -> u32 {
-    let (_, contents) = parse(input).unwrap();
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_all(input, parse_instructions)
+}
 
-    let mut lights_map = HashMap::new();
+pub fn solution_1(contents: &[Instruction]) -> u32 {
+    let mut lights_map = Grid::new(SIZE, SIZE, false);
 
     for (command, (from, to)) in contents {
-        let min_x = from.0.min(to.0);
-        let max_x = from.0.max(to.0);
+        let min_x = from.x.min(to.x);
+        let max_x = from.x.max(to.x);
 
-        let min_y = from.1.min(to.1);
-        let max_y = from.1.max(to.1);
+        let min_y = from.y.min(to.y);
+        let max_y = from.y.max(to.y);
 
         for (x, y) in (min_x..=max_x).cartesian_product(min_y..=max_y) {
-            let entry = lights_map.entry((x, y)).or_insert(false);
+            let entry = &mut lights_map[Point2::new(x, y)];
 
             match command {
                 Command::Toggle => {
@@ -71,23 +82,21 @@ fn solution_1(input: &str) -> u32 {
         }
     }
 
-    (lights_map.values().filter(|&x| *x == true).count()) as u32
+    (lights_map.values().filter(|&x| *x).count()) as u32
 }
 
-fn solution_2(input: &str) -> u32 {
-    let (_, contents) = parse(input).unwrap();
-
-    let mut lights_map = HashMap::new();
+pub fn solution_2(contents: &[Instruction]) -> u32 {
+    let mut lights_map = Grid::new(SIZE, SIZE, 0u32);
 
     for (command, (from, to)) in contents {
-        let min_x = from.0.min(to.0);
-        let max_x = from.0.max(to.0);
+        let min_x = from.x.min(to.x);
+        let max_x = from.x.max(to.x);
 
-        let min_y = from.1.min(to.1);
-        let max_y = from.1.max(to.1);
+        let min_y = from.y.min(to.y);
+        let max_y = from.y.max(to.y);
 
         for (x, y) in (min_x..=max_x).cartesian_product(min_y..=max_y) {
-            let entry = lights_map.entry((x, y)).or_insert(0u32);
+            let entry = &mut lights_map[Point2::new(x, y)];
 
             match command {
                 Command::Toggle => {
@@ -103,34 +112,55 @@ fn solution_2(input: &str) -> u32 {
         }
     }
 
-    lights_map.values().sum::<u32>() as u32
+    lights_map.values().sum::<u32>()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2015;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Probably a Fire Hazard";
+
+    type Params = ();
+    type Input<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::input_or_skip;
 
-    #[test]
-    fn solution_1_example() {
-        assert_eq!(1_000_000, solution_1("turn on 0,0 through 999,999"));
-        assert_eq!(1000, solution_1("toggle 0,0 through 999,0"));
-    }
+    use super::*;
 
     #[test]
     fn solution_1_input() {
-        let input = include_str!("input/day6.txt");
-        assert_eq!(999_999, solution_1(input));
+        let input = input_or_skip!(crate::inputs().load(6));
+        assert_eq!(999_999, solution_1(&parse(&input).unwrap()));
     }
 
     #[test]
-    fn solution_2_example() {
-        assert_eq!(2_000_000, solution_2("toggle 0,0 through 999,999"));
-        assert_eq!(1, solution_2("turn on 0,0 through 0,0"));
+    fn parse_rejects_lights_outside_the_grid() {
+        let err = parse("turn on 0,0 through 999,999\ntoggle 0,0 through 1000,0").unwrap_err();
+
+        assert_eq!((2, 1), (err.line, err.column));
+        assert_eq!("toggle 0,0 through 1000,0", err.text);
     }
 
     #[test]
     fn solution_2_input() {
-        let input = include_str!("input/day6.txt");
-        assert_eq!(14_687_245, solution_2(input));
+        let input = input_or_skip!(crate::inputs().load(6));
+        assert_eq!(14_687_245, solution_2(&parse(&input).unwrap()));
     }
 }
@@ -1,7 +1,33 @@
-#![allow(dead_code)]
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
\ No newline at end of file
+#![allow(dead_code)]
+
+use aoc_common::{Inputs, Puzzle};
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+];
+
+aoc_common::example_tests! {
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+}
+
+/// This year's personal puzzle inputs, read from `src/input` unless `AOC_INPUT_DIR` is set.
+pub fn inputs() -> Inputs {
+    Inputs::for_year(2015, concat!(env!("CARGO_MANIFEST_DIR"), "/src/input"))
+}
@@ -1,95 +1,84 @@
-use nom::{
-    character::complete::{line_ending, newline},
-    combinator::map,
-    multi::separated_list1,
-    sequence::pair,
-    IResult,
-};
-
-fn parse_group(input: &str) -> IResult<&str, u32> {
-    map(
-        separated_list1(newline, nom::character::complete::u32),
-        |val| val.into_iter().sum(),
-    )(input)
-}
-
-fn parse_whole(input: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(pair(line_ending, line_ending), parse_group)(input)
-}
-
-fn solution_1(input: &str) -> Option<u32> {
-    get_calories(input).max()
-}
-
-fn solution_2(input: &str) -> u32 {
-    let mut calories = get_calories(input).collect::<Vec<u32>>();
-
-    calories.sort();
-    calories.reverse();
-    calories.into_iter().take(3).sum::<u32>()
-}
-
-fn get_calories(input: &str) -> impl Iterator<Item = u32> + '_ {
-    let (_, result) = parse_whole(input).unwrap();
-
-    result.into_iter()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000";
-
-        assert_eq!(24000, solution_1(input).unwrap());
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day1.txt");
-
-        assert_eq!(67633, solution_1(input).unwrap());
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "1000
-2000
-3000
-
-4000
-
-5000
-6000
-
-7000
-8000
-9000
-
-10000";
-
-        assert_eq!(45000, solution_2(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day1.txt");
-
-        assert_eq!(199628, solution_2(input));
-    }
-}
+use aoc_common::{parse::parse_all, Answer, ParseError, Solution, SolveError};
+use nom::{
+    character::complete::line_ending, combinator::map, multi::separated_list1, sequence::pair,
+    IResult,
+};
+
+fn parse_group(input: &str) -> IResult<&str, u32> {
+    map(
+        separated_list1(line_ending, nom::character::complete::u32),
+        |val| val.into_iter().sum(),
+    )(input)
+}
+
+fn parse_whole(input: &str) -> IResult<&str, Vec<u32>> {
+    separated_list1(pair(line_ending, line_ending), parse_group)(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    parse_all(input, parse_whole)
+}
+
+pub fn solution_1(calories: &[u32]) -> Option<u32> {
+    calories.iter().copied().max()
+}
+
+pub fn solution_2(calories: &[u32]) -> u32 {
+    let mut calories = calories.to_vec();
+
+    calories.sort();
+    calories.reverse();
+    calories.into_iter().take(3).sum::<u32>()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Params = ();
+    type Input<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(1));
+
+        assert_eq!(67633, solution_1(&parse(&input).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn parse_reports_non_numbers() {
+        assert_eq!(
+            Err(ParseError::new(4, 1, "abc", "unexpected trailing input")),
+            parse("1000\n2000\n\nabc")
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(1));
+
+        assert_eq!(199628, solution_2(&parse(&input).unwrap()));
+    }
+}
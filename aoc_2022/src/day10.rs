@@ -1,442 +1,243 @@
-enum Instruction {
-    AddX(i32),
-    NoOp,
-}
-
-impl Instruction {
-    fn get_cycle(&self) -> i32 {
-        match &self {
-            Instruction::NoOp => 1,
-            Instruction::AddX(_) => 2,
-        }
-    }
-
-    fn value(&self) -> i32 {
-        match self {
-            Instruction::NoOp => 0,
-            Instruction::AddX(val) => *val,
-        }
-    }
-}
-
-struct Cycle {
-    cycle: i32,
-    value: i32,
-}
-
-impl Cycle {
-    fn new(cycle: i32, value: i32) -> Self {
-        Self { cycle, value }
-    }
-}
-
-fn get_cycles(input: &str) -> Vec<Cycle> {
-    let instructions = input
-        .trim()
-        .lines()
-        .map(|line| {
-            let mut split = line.trim().split_whitespace();
-
-            match split.next().unwrap().to_lowercase().as_str() {
-                "noop" => Instruction::NoOp,
-                "addx" => Instruction::AddX(split.next().unwrap().parse::<i32>().unwrap()),
-                _ => unreachable!(),
-            }
-        })
-        .collect::<Vec<_>>();
-
-    let mut value = 1;
-    let mut iterations = vec![];
-
-    let mut cycle = 1;
-    for (_, instruction) in instructions.iter().enumerate() {
-        let instruction_cycle = instruction.get_cycle();
-
-        for cycle_index in 1..=instruction_cycle {
-            iterations.push(Cycle::new(cycle, value));
-
-            if cycle_index == instruction_cycle {
-                value += instruction.value();
-            }
-
-            cycle += 1;
-        }
-    }
-
-    iterations
-}
-
-fn solution_1(input: &str) -> i32 {
-    let cycles = get_cycles(input);
-
-    let ranges = &[20, 60, 100, 140, 180, 220];
-    let mut result = 0;
-    for range in ranges {
-        let cycle: &Cycle = &cycles[range - 1];
-
-        let cycle_result = cycle.cycle * cycle.value;
-
-        result += cycle_result;
-    }
-
-    result
-}
-
-fn solution_2(input: &str) -> String {
-    let characters = [" ", "█"];
-    let cycles = get_cycles(input);
-
-    let mut screen = String::default();
-
-    for cycle in cycles {
-        let sprite = cycle.value;
-        let column = (cycle.cycle - 1) % 40;
-
-        let char = characters[((sprite - column).abs() < 2) as usize];
-        screen.push_str(char);
-    }
-
-    let result = screen
-        .chars()
-        .collect::<Vec<_>>()
-        .chunks(40)
-        .map(|char| char.iter().collect::<String>())
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    println!("{result}");
-
-    result
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "addx 15
-        addx -11
-        addx 6
-        addx -3
-        addx 5
-        addx -1
-        addx -8
-        addx 13
-        addx 4
-        noop
-        addx -1
-        addx 5
-        addx -1
-        addx 5
-        addx -1
-        addx 5
-        addx -1
-        addx 5
-        addx -1
-        addx -35
-        addx 1
-        addx 24
-        addx -19
-        addx 1
-        addx 16
-        addx -11
-        noop
-        noop
-        addx 21
-        addx -15
-        noop
-        noop
-        addx -3
-        addx 9
-        addx 1
-        addx -3
-        addx 8
-        addx 1
-        addx 5
-        noop
-        noop
-        noop
-        noop
-        noop
-        addx -36
-        noop
-        addx 1
-        addx 7
-        noop
-        noop
-        noop
-        addx 2
-        addx 6
-        noop
-        noop
-        noop
-        noop
-        noop
-        addx 1
-        noop
-        noop
-        addx 7
-        addx 1
-        noop
-        addx -13
-        addx 13
-        addx 7
-        noop
-        addx 1
-        addx -33
-        noop
-        noop
-        noop
-        addx 2
-        noop
-        noop
-        noop
-        addx 8
-        noop
-        addx -1
-        addx 2
-        addx 1
-        noop
-        addx 17
-        addx -9
-        addx 1
-        addx 1
-        addx -3
-        addx 11
-        noop
-        noop
-        addx 1
-        noop
-        addx 1
-        noop
-        noop
-        addx -13
-        addx -19
-        addx 1
-        addx 3
-        addx 26
-        addx -30
-        addx 12
-        addx -1
-        addx 3
-        addx 1
-        noop
-        noop
-        noop
-        addx -9
-        addx 18
-        addx 1
-        addx 2
-        noop
-        noop
-        addx 9
-        noop
-        noop
-        noop
-        addx -1
-        addx 2
-        addx -37
-        addx 1
-        addx 3
-        noop
-        addx 15
-        addx -21
-        addx 22
-        addx -6
-        addx 1
-        noop
-        addx 2
-        addx 1
-        noop
-        addx -10
-        noop
-        noop
-        addx 20
-        addx 1
-        addx 2
-        addx 2
-        addx -6
-        addx -11
-        noop
-        noop
-        noop";
-
-        assert_eq!(13140, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day10.txt");
-
-        assert_eq!(13180, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "addx 15
-        addx -11
-        addx 6
-        addx -3
-        addx 5
-        addx -1
-        addx -8
-        addx 13
-        addx 4
-        noop
-        addx -1
-        addx 5
-        addx -1
-        addx 5
-        addx -1
-        addx 5
-        addx -1
-        addx 5
-        addx -1
-        addx -35
-        addx 1
-        addx 24
-        addx -19
-        addx 1
-        addx 16
-        addx -11
-        noop
-        noop
-        addx 21
-        addx -15
-        noop
-        noop
-        addx -3
-        addx 9
-        addx 1
-        addx -3
-        addx 8
-        addx 1
-        addx 5
-        noop
-        noop
-        noop
-        noop
-        noop
-        addx -36
-        noop
-        addx 1
-        addx 7
-        noop
-        noop
-        noop
-        addx 2
-        addx 6
-        noop
-        noop
-        noop
-        noop
-        noop
-        addx 1
-        noop
-        noop
-        addx 7
-        addx 1
-        noop
-        addx -13
-        addx 13
-        addx 7
-        noop
-        addx 1
-        addx -33
-        noop
-        noop
-        noop
-        addx 2
-        noop
-        noop
-        noop
-        addx 8
-        noop
-        addx -1
-        addx 2
-        addx 1
-        noop
-        addx 17
-        addx -9
-        addx 1
-        addx 1
-        addx -3
-        addx 11
-        noop
-        noop
-        addx 1
-        noop
-        addx 1
-        noop
-        noop
-        addx -13
-        addx -19
-        addx 1
-        addx 3
-        addx 26
-        addx -30
-        addx 12
-        addx -1
-        addx 3
-        addx 1
-        noop
-        noop
-        noop
-        addx -9
-        addx 18
-        addx 1
-        addx 2
-        noop
-        noop
-        addx 9
-        noop
-        noop
-        noop
-        addx -1
-        addx 2
-        addx -37
-        addx 1
-        addx 3
-        noop
-        addx 15
-        addx -21
-        addx 22
-        addx -6
-        addx 1
-        noop
-        addx 2
-        addx 1
-        noop
-        addx -10
-        noop
-        noop
-        addx 20
-        addx 1
-        addx 2
-        addx 2
-        addx -6
-        addx -11
-        noop
-        noop
-        noop";
-
-        let result = "██  ██  ██  ██  ██  ██  ██  ██  ██  ██  \n███   ███   ███   ███   ███   ███   ███ \n████    ████    ████    ████    ████    \n█████     █████     █████     █████     \n██████      ██████      ██████      ████\n███████       ███████       ███████";
-
-        assert_eq!(result, solution_2(input).trim());
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day10.txt");
-
-        let result = "████ ████ ████  ██  █  █   ██  ██  ███  
-█       █ █    █  █ █  █    █ █  █ █  █ 
-███    █  ███  █    ████    █ █  █ ███  
-█     █   █    █    █  █    █ ████ █  █ 
-█    █    █    █  █ █  █ █  █ █  █ █  █ 
-████ ████ █     ██  █  █  ██  █  █ ███  ";
-
-        assert_eq!(result.trim(), solution_2(input).trim());
-    }
-}
+use std::fmt;
+
+use aoc_common::{
+    ocr,
+    vm::{
+        Flow, Instruction, InstructionSet, InstructionSpec, Machine, Operand, OperandKind,
+        Registers,
+    },
+    Answer, Grid, OcrError, ParseError, Point2, Solution, SolveError,
+};
+
+fn addx(registers: &mut Registers, operands: &[Operand]) -> Flow {
+    registers.set(X, registers.get(X) + registers.value(operands[0]));
+    Flow::Next
+}
+
+fn noop(_: &mut Registers, _: &[Operand]) -> Flow {
+    Flow::Next
+}
+
+/// The index of the CPU's only register.
+const X: usize = 0;
+
+pub static CPU: InstructionSet = InstructionSet {
+    registers: &["x"],
+    instructions: &[
+        InstructionSpec {
+            name: "noop",
+            operands: &[],
+            cycles: 1,
+            execute: noop,
+        },
+        InstructionSpec {
+            name: "addx",
+            operands: &[OperandKind::Value],
+            cycles: 2,
+            execute: addx,
+        },
+    ],
+};
+
+pub fn parse(input: &str) -> Result<Vec<Instruction<'static>>, ParseError> {
+    CPU.parse(input)
+}
+
+/// Runs the program on the CPU, whose X register starts at 1.
+pub fn run<'a>(instructions: &'a [Instruction<'a>]) -> Machine<'a> {
+    Machine::new(&CPU, instructions).with_register("x", 1)
+}
+
+pub fn solution_1(instructions: &[Instruction]) -> i64 {
+    run(instructions)
+        .filter(|tick| tick.cycle % 40 == 20 && tick.cycle <= 220)
+        .map(|tick| tick.cycle as i64 * tick.registers.get(X))
+        .sum()
+}
+
+/// The CPU's screen, which draws one pixel per cycle, left to right and top to bottom. A pixel is
+/// lit when the 3 pixel wide sprite, centred on the X register, covers it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crt {
+    pixels: Grid<bool>,
+}
+
+impl Crt {
+    pub const WIDTH: usize = 40;
+    pub const HEIGHT: usize = 6;
+
+    pub fn new() -> Self {
+        Self {
+            pixels: Grid::new(Self::WIDTH, Self::HEIGHT, false),
+        }
+    }
+
+    /// Draws the pixel of `cycle`, counting from 1, with the sprite centred on column `sprite`.
+    /// Cycles past the last pixel start over from the top.
+    pub fn draw(&mut self, cycle: u64, sprite: i64) {
+        let index = (cycle.max(1) - 1) as usize % (Self::WIDTH * Self::HEIGHT);
+        let (x, y) = (index % Self::WIDTH, index / Self::WIDTH);
+
+        self.pixels[Point2::new(x, y)] = (sprite - x as i64).abs() < 2;
+    }
+
+    pub fn pixels(&self) -> &Grid<bool> {
+        &self.pixels
+    }
+
+    /// Reads the letters on the screen.
+    pub fn read(&self) -> Result<String, OcrError> {
+        ocr::read_letters(&self.pixels)
+    }
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draws lit pixels as `█` and dark ones as spaces.
+impl fmt::Display for Crt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.pixels.map(|&lit| match lit {
+                true => '█',
+                false => ' ',
+            })
+        )
+    }
+}
+
+fn render(instructions: &[Instruction]) -> Crt {
+    let mut crt = Crt::new();
+
+    for tick in run(instructions) {
+        crt.draw(tick.cycle, tick.registers.get(X));
+    }
+
+    crt
+}
+
+/// The letters on the screen, or the screen itself when it does not show letters, like the example
+/// program's test pattern.
+pub fn solution_2(instructions: &[Instruction]) -> String {
+    let crt = render(instructions);
+
+    crt.read().unwrap_or_else(|_| crt.to_string())
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Params = ();
+    type Input<'a> = Vec<Instruction<'static>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{
+        input_or_skip,
+        vm::{Breakpoint, Debugger, Stop, Tick},
+    };
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(10));
+
+        assert_eq!(13180, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_instructions() {
+        let err = parse("noop\naddx 3\nmulx 2").err().unwrap();
+
+        assert_eq!(
+            ParseError::new(3, 1, "mulx 2", "expected `noop` or `addx <value>`"),
+            err
+        );
+        assert!(parse("addx").is_err());
+        assert_eq!(
+            Err(ParseError::new(1, 6, "x", "expected a number")),
+            parse("addx x").map(|program| program.len())
+        );
+    }
+
+    #[test]
+    fn debug_the_example() {
+        let program = parse("noop\naddx 3\naddx -5").unwrap();
+        let mut debugger = Debugger::new(run(&program));
+
+        debugger.add_breakpoint(Breakpoint::Register {
+            register: X,
+            value: 4,
+        });
+
+        assert!(matches!(
+            debugger.run(),
+            Stop::Breakpoint(
+                _,
+                Tick {
+                    cycle: 4,
+                    pc: 2,
+                    ..
+                }
+            )
+        ));
+        assert_eq!(Some(4), debugger.step().map(|tick| tick.registers.get(X)));
+        assert_eq!(Stop::Halted, debugger.run());
+        assert_eq!(Some(-1), debugger.machine().register("x"));
+        assert_eq!(
+            "    1     0  noop              x=1\n    2     1  addx 3            x=1\n",
+            debugger
+                .dump_trace()
+                .lines()
+                .take(2)
+                .map(|line| format!("{line}\n"))
+                .collect::<String>()
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(10));
+
+        assert_eq!("EZFCHJAB", solution_2(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn crt_draws_sprite_overlap() {
+        let mut crt = Crt::new();
+
+        crt.draw(1, 1);
+        crt.draw(2, 5);
+        crt.draw(43, 3);
+        crt.draw(241, 1);
+
+        assert_eq!(Some(&true), crt.pixels().get(Point2::new(0, 0)));
+        assert_eq!(Some(&false), crt.pixels().get(Point2::new(1, 0)));
+        assert_eq!(Some(&true), crt.pixels().get(Point2::new(2, 1)));
+        assert!(crt.to_string().starts_with("█   "));
+        assert!(crt.read().is_err());
+    }
+}
@@ -1,240 +1,1161 @@
-use std::collections::VecDeque;
-
-use itertools::Itertools;
-
-#[derive(Debug)]
-enum OperationValue {
-    By(u64),
-    ByOriginal,
-}
-
-#[derive(Debug)]
-enum Operation {
-    Add(OperationValue),
-    Multiply(OperationValue),
-}
-
-#[derive(Debug)]
-struct Test {
-    value: u64,
-    true_target: usize,
-    false_target: usize,
-}
-
-#[derive(Debug)]
-struct Monkey {
-    id: u32,
-    gifts: VecDeque<u64>,
-    inspections: u64,
-    operation: Operation,
-    test: Test,
-}
-
-impl Monkey {
-    fn new(id: u32, gifts: Vec<u64>, operation: Operation, test: Test) -> Self {
-        Self {
-            id,
-            gifts: VecDeque::from_iter(gifts.into_iter()),
-            inspections: 0,
-            operation,
-            test,
-        }
-    }
-
-    fn inspect_item<F>(&mut self, f: &F) -> u64
-    where
-        F: Fn(u64) -> u64,
-    {
-        self.inspections += 1;
-        let gift = self.gifts.pop_front().unwrap();
-
-        let gift = match &self.operation {
-            Operation::Add(val) => match val {
-                OperationValue::By(by) => gift + *by,
-                OperationValue::ByOriginal => gift + gift,
-            },
-            Operation::Multiply(val) => match val {
-                OperationValue::By(by) => gift * *by,
-                OperationValue::ByOriginal => gift * gift,
-            },
-        };
-
-        f(gift)
-    }
-}
-
-fn parse_input(input: &str) -> Vec<Monkey> {
-    let monkey_groups = input.trim().split("\n\n");
-
-    let mut monkeys = vec![];
-
-    for group_item in monkey_groups {
-        let mut group_lines = group_item.trim().lines().map(|line| line.trim());
-
-        let monkey_id = group_lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .skip(1)
-            .take(1)
-            .next()
-            .unwrap()
-            .replace(":", "")
-            .parse::<u32>()
-            .unwrap();
-
-        let starting_items = group_lines
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .skip(2)
-            .map(|item| item.replace(",", "").parse::<u64>().unwrap())
-            .collect::<Vec<_>>();
-
-        let mut operation = group_lines.next().unwrap().split_whitespace().skip(4);
-
-        let operation_sign = operation.next().unwrap();
-        let value = operation.next().unwrap();
-
-        let value = match value {
-            "old" => OperationValue::ByOriginal,
-            val => OperationValue::By(val.parse::<u64>().unwrap()),
-        };
-
-        let operation = match operation_sign {
-            "+" => Operation::Add(value),
-            "*" => Operation::Multiply(value),
-            _ => panic!(),
-        };
-
-        let test = group_lines.collect::<Vec<_>>();
-        let mut test = test.iter();
-        let condition = test
-            .next()
-            .unwrap()
-            .trim()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<u64>()
-            .unwrap();
-
-        let true_expression = test
-            .next()
-            .unwrap()
-            .trim()
-            .chars()
-            .last()
-            .unwrap()
-            .to_string()
-            .parse::<usize>()
-            .unwrap();
-
-        let false_expression = test
-            .next()
-            .unwrap()
-            .trim()
-            .chars()
-            .last()
-            .unwrap()
-            .to_string()
-            .parse::<usize>()
-            .unwrap();
-
-        let monkey = Monkey::new(
-            monkey_id,
-            starting_items,
-            operation,
-            Test {
-                value: condition,
-                true_target: true_expression,
-                false_target: false_expression,
-            },
-        );
-
-        monkeys.push(monkey);
-    }
-
-    monkeys
-}
-
-fn play_game<F>(monkeys: &mut Vec<Monkey>, rounds: u32, f: F) -> u64
-where
-    F: Fn(u64) -> u64,
-{
-    for _ in 1..=rounds {
-        for index in 0..monkeys.len() {
-            for _ in 0..monkeys[index].gifts.len() {
-                let monkey = monkeys.get_mut(index).unwrap();
-                let item = monkey.inspect_item(&f);
-
-                let recipient_monkey_id = if item % (monkey.test.value as u64) == 0 {
-                    monkey.test.true_target
-                } else {
-                    monkey.test.false_target
-                };
-
-                let recipient = monkeys.get_mut(recipient_monkey_id).unwrap();
-                recipient.gifts.push_back(item);
-            }
-        }
-    }
-
-    monkeys
-        .iter()
-        .map(|monkey| monkey.inspections)
-        .sorted()
-        .rev()
-        .take(2)
-        .product()
-}
-
-fn solution_1(input: &str) -> u64 {
-    let mut monkeys = parse_input(input);
-
-    let result = play_game(&mut monkeys, 20, |gift| gift / 3);
-
-    result
-}
-
-fn solution_2(input: &str) -> u64 {
-    let mut monkeys = parse_input(input);
-
-    let lcm = monkeys
-        .iter()
-        .map(|monkey| monkey.test.value as u64)
-        .product::<u64>();
-
-    let result = play_game(&mut monkeys, 10000, |gift| gift % lcm);
-
-    result
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let example_input = include_str!("input/example/day11.txt");
-        assert_eq!(10605, solution_1(example_input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let example_input = include_str!("input/day11.txt");
-        assert_eq!(121450, solution_1(example_input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let example_input = include_str!("input/example/day11.txt");
-        assert_eq!(2713310158, solution_2(example_input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let example_input = include_str!("input/day11.txt");
-        assert_eq!(28244037010, solution_2(example_input));
-    }
-}
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+use aoc_common::{parse::parse_all, Answer, ParamError, ParseError, Part, Solution, SolveError};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, multispace1, one_of, space0, space1},
+    combinator::{cut, eof, map, map_res, peek, value, verify},
+    error::{context, VerboseError},
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl Operator {
+    fn symbol(self) -> char {
+        match self {
+            Operator::Add => '+',
+            Operator::Subtract => '-',
+            Operator::Multiply => '*',
+            Operator::Divide => '/',
+            Operator::Remainder => '%',
+        }
+    }
+}
+
+/// The right hand side of a monkey's `Operation: new = ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    Old,
+    Constant(u64),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalError {
+    Overflow,
+    /// The worry level would go below 0.
+    Underflow,
+    DivisionByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Overflow => write!(f, "the worry level overflows"),
+            EvalError::Underflow => write!(f, "the worry level goes below 0"),
+            EvalError::DivisionByZero => write!(f, "the worry level is divided by 0"),
+        }
+    }
+}
+
+impl Expression {
+    fn binary(left: Expression, operator: Operator, right: Expression) -> Self {
+        Expression::Binary(Box::new(left), operator, Box::new(right))
+    }
+
+    /// Works out the new worry level, with intermediate results as large as a `u128`.
+    pub fn eval(&self, old: u64) -> Result<u64, EvalError> {
+        self.eval_wide(old)?
+            .try_into()
+            .map_err(|_| EvalError::Overflow)
+    }
+
+    fn eval_wide(&self, old: u64) -> Result<u128, EvalError> {
+        let Expression::Binary(left, operator, right) = self else {
+            return Ok(match self {
+                Expression::Constant(value) => *value as u128,
+                _ => old as u128,
+            });
+        };
+
+        let (left, right) = (left.eval_wide(old)?, right.eval_wide(old)?);
+
+        match operator {
+            Operator::Add => left.checked_add(right).ok_or(EvalError::Overflow),
+            Operator::Subtract => left.checked_sub(right).ok_or(EvalError::Underflow),
+            Operator::Multiply => left.checked_mul(right).ok_or(EvalError::Overflow),
+            Operator::Divide => left.checked_div(right).ok_or(EvalError::DivisionByZero),
+            Operator::Remainder => left.checked_rem(right).ok_or(EvalError::DivisionByZero),
+        }
+    }
+
+    /// Adds the moduli the expression needs worry levels to be kept modulo for its result to stay
+    /// right modulo them, or returns `false` when no modulus works.
+    ///
+    /// Adding and multiplying work modulo anything, and `% n` works modulo multiples of `n`.
+    /// Dividing does not, and neither does subtracting: modulo a number there is no telling
+    /// whether the worry level went below 0, which [`Expression::eval`] reports as an error.
+    fn ring_moduli(&self, moduli: &mut Vec<u64>) -> bool {
+        match self {
+            Expression::Old | Expression::Constant(_) => true,
+            Expression::Binary(left, operator, right) => {
+                let compatible = match (operator, &**right) {
+                    (Operator::Divide | Operator::Subtract, _) => false,
+                    (Operator::Remainder, Expression::Constant(modulus)) => {
+                        moduli.push(*modulus);
+                        *modulus > 0
+                    }
+                    (Operator::Remainder, _) => false,
+                    _ => true,
+                };
+
+                compatible && left.ring_moduli(moduli) && right.ring_moduli(moduli)
+            }
+        }
+    }
+
+    /// Works out the new worry level modulo `modulus`, which must be a multiple of every one
+    /// [`Expression::ring_moduli`] asked for.
+    fn eval_modulo(&self, old: u64, modulus: u64) -> u64 {
+        let wide = modulus as u128;
+
+        let result = match self {
+            Expression::Old => old as u128 % wide,
+            Expression::Constant(value) => *value as u128 % wide,
+            Expression::Binary(left, operator, right) => {
+                let left = left.eval_modulo(old, modulus) as u128;
+
+                match (operator, &**right) {
+                    (Operator::Remainder, Expression::Constant(divisor)) => left % *divisor as u128,
+                    (Operator::Remainder | Operator::Divide | Operator::Subtract, _) => {
+                        unreachable!("checked by `ring_moduli`")
+                    }
+                    (operator, right) => {
+                        let right = right.eval_modulo(old, modulus) as u128;
+
+                        match operator {
+                            Operator::Add => (left + right) % wide,
+                            _ => left * right % wide,
+                        }
+                    }
+                }
+            }
+        };
+
+        result as u64
+    }
+}
+
+/// Writes the expression back in the notes' form, with brackets only where they are needed.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Old => write!(f, "old"),
+            Expression::Constant(value) => write!(f, "{value}"),
+            Expression::Binary(left, operator, right) => {
+                let precedence = |expression: &Expression| match expression {
+                    Expression::Binary(_, Operator::Add | Operator::Subtract, _) => 1,
+                    Expression::Binary(..) => 2,
+                    _ => 3,
+                };
+                let own = precedence(self);
+
+                // Operators are left associative, so the right operand needs brackets on a tie.
+                match precedence(left) < own {
+                    true => write!(f, "({left})")?,
+                    false => write!(f, "{left}")?,
+                }
+
+                write!(f, " {} ", operator.symbol())?;
+
+                match precedence(right) <= own {
+                    true => write!(f, "({right})"),
+                    false => write!(f, "{right}"),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonkeyError {
+    Eval {
+        monkey: usize,
+        worry: u64,
+        err: EvalError,
+    },
+    /// Worry levels cannot be kept small, since the monkey's operation does not give the same
+    /// results modulo the tests' divisors.
+    NotRingCompatible { monkey: usize, operation: String },
+    /// The divisors have no common multiple that fits a `u64`.
+    ModulusOverflow,
+    /// A monkey inspects more items than fit a `u64`.
+    InspectionOverflow,
+}
+
+impl fmt::Display for MonkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MonkeyError::Eval { monkey, worry, err } => {
+                write!(f, "monkey {monkey} inspecting an item of worry {worry}: {err}")
+            }
+            MonkeyError::NotRingCompatible { monkey, operation } => write!(
+                f,
+                "monkey {monkey}'s operation `{operation}` cannot be worked out modulo the test divisors"
+            ),
+            MonkeyError::ModulusOverflow => {
+                write!(f, "the test divisors have no common multiple that fits in 64 bits")
+            }
+            MonkeyError::InspectionOverflow => {
+                write!(f, "a monkey inspects more items than fit in 64 bits")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MonkeyError {}
+
+#[derive(Debug, Clone)]
+struct Test {
+    value: u64,
+    true_target: usize,
+    false_target: usize,
+}
+
+impl Test {
+    fn target(&self, worry: u64) -> usize {
+        match worry % self.value {
+            0 => self.true_target,
+            _ => self.false_target,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    id: u32,
+    /// The worry levels of the items the monkey starts with.
+    items: Vec<u64>,
+    operation: Expression,
+    test: Test,
+}
+
+/// How worry levels are kept in check after each inspection.
+#[derive(Debug, Clone, Copy)]
+enum Relief {
+    /// Divided by 3, as long as items are not damaged.
+    Divide,
+    /// Kept modulo a multiple of every test's divisor.
+    Modulo(u64),
+}
+
+impl Relief {
+    /// Part 1 divides worry levels, part 2 keeps them modulo the divisors.
+    fn for_part(monkeys: &[Monkey], part: Part) -> Result<Self, MonkeyError> {
+        match part {
+            Part::One => Ok(Relief::Divide),
+            Part::Two => Ok(Relief::Modulo(modulus(monkeys)?)),
+        }
+    }
+}
+
+impl Monkey {
+    fn new(id: u32, items: Vec<u64>, operation: Expression, test: Test) -> Self {
+        Self {
+            id,
+            items,
+            operation,
+            test,
+        }
+    }
+
+    /// The new worry level of an item once inspected.
+    fn inspect(&self, worry: u64, relief: Relief) -> Result<u64, MonkeyError> {
+        match relief {
+            Relief::Divide => self
+                .operation
+                .eval(worry)
+                .map(|worry| worry / 3)
+                .map_err(|err| MonkeyError::Eval {
+                    monkey: self.id as usize,
+                    worry,
+                    err,
+                }),
+            Relief::Modulo(modulus) => Ok(self.operation.eval_modulo(worry, modulus)),
+        }
+    }
+}
+
+type NomResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
+
+/// The notes on one monkey, keeping the text of the ids to point at when they do not add up.
+struct Notes<'a> {
+    id: &'a str,
+    monkey: Monkey,
+    targets: [&'a str; 2],
+}
+
+fn number<'a, T: FromStr>(input: &'a str) -> NomResult<'a, (&'a str, T)> {
+    context(
+        "expected a number",
+        map_res(digit1, |digits: &str| {
+            digits.parse().map(|value| (digits, value))
+        }),
+    )(input)
+}
+
+/// A line of notes starting with `$label`, on a new line and with any indentation, followed by
+/// what `$value` parses.
+macro_rules! field {
+    ($label:literal, $value:expr) => {
+        preceded(
+            tuple((
+                line_ending,
+                space0,
+                context(concat!("expected `", $label, "`"), tag($label)),
+                space0,
+            )),
+            $value,
+        )
+    };
+}
+
+fn operand(input: &str) -> NomResult<'_, Expression> {
+    let brackets = delimited(
+        pair(char('('), space0),
+        expression,
+        pair(space0, context("expected `)`", char(')'))),
+    );
+
+    context(
+        "expected `old`, a number or `(`",
+        alt((
+            value(Expression::Old, tag("old")),
+            map(number, |(_, value)| Expression::Constant(value)),
+            brackets,
+        )),
+    )(input)
+}
+
+/// Parses operands separated by one of `operators`, all of the same precedence.
+fn operations<'a>(
+    operators: &'static str,
+    mut operand: impl FnMut(&'a str) -> NomResult<'a, Expression>,
+) -> impl FnMut(&'a str) -> NomResult<'a, Expression> {
+    move |input| {
+        let (mut input, mut expression) = operand(input)?;
+
+        while let Ok((rest, operator)) =
+            delimited(space0::<_, VerboseError<_>>, one_of(operators), space0)(input)
+        {
+            let operator = match operator {
+                '+' => Operator::Add,
+                '-' => Operator::Subtract,
+                '*' => Operator::Multiply,
+                '/' => Operator::Divide,
+                _ => Operator::Remainder,
+            };
+
+            let (rest, right) = operand(rest)?;
+            (input, expression) = (rest, Expression::binary(expression, operator, right));
+        }
+
+        Ok((input, expression))
+    }
+}
+
+fn expression(input: &str) -> NomResult<'_, Expression> {
+    operations("+-", operations("*/%", operand))(input)
+}
+
+/// An expression taking up the rest of its line.
+fn operation(input: &str) -> NomResult<'_, Expression> {
+    terminated(
+        expression,
+        pair(
+            space0,
+            context(
+                "expected `+`, `-`, `*`, `/` or `%`",
+                peek(alt((line_ending, eof))),
+            ),
+        ),
+    )(input)
+}
+
+fn monkey(input: &str) -> NomResult<'_, Notes<'_>> {
+    let items = separated_list0(pair(char(','), space0), map(number, |(_, item)| item));
+
+    // Anything going wrong after `Monkey` is an error in that monkey's notes, rather than the end
+    // of the list of monkeys.
+    let notes = tuple((
+        terminated(number, context("expected `:`", char(':'))),
+        field!("Starting items:", items),
+        field!("Operation: new =", operation),
+        field!(
+            "Test: divisible by",
+            context(
+                "expected a divisor above 0",
+                verify(number, |&(_, divisor): &(&str, u64)| divisor > 0)
+            )
+        ),
+        field!("If true: throw to monkey", number),
+        field!("If false: throw to monkey", number),
+    ));
+
+    map(
+        preceded(pair(tag("Monkey"), space1), cut(notes)),
+        |(
+            (id, number),
+            items,
+            operation,
+            (_, value),
+            (if_true, true_target),
+            (if_false, false_target),
+        )| Notes {
+            id,
+            monkey: Monkey::new(
+                number,
+                items,
+                operation,
+                Test {
+                    value,
+                    true_target,
+                    false_target,
+                },
+            ),
+            targets: [if_true, if_false],
+        },
+    )(input)
+}
+
+/// Parses the notes on every monkey, which must be numbered from 0 in order and only throw to
+/// monkeys that exist.
+pub fn parse(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let notes = parse_all(input, separated_list1(multispace1, monkey))?;
+    let count = notes.len();
+
+    for (index, notes) in notes.iter().enumerate() {
+        if notes.monkey.id as usize != index {
+            return Err(ParseError::at(
+                input,
+                notes.id,
+                format!("expected monkey {index}"),
+            ));
+        }
+
+        let test = &notes.monkey.test;
+
+        for (target, text) in [test.true_target, test.false_target]
+            .into_iter()
+            .zip(notes.targets)
+        {
+            if target >= count {
+                return Err(ParseError::at(
+                    input,
+                    text,
+                    format!("there is no monkey {target}"),
+                ));
+            }
+        }
+    }
+
+    Ok(notes.into_iter().map(|notes| notes.monkey).collect())
+}
+
+/// An item thrown from one monkey to another. Items are numbered from 0 in the order of the notes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throw {
+    /// Counted from 1.
+    pub round: u32,
+    pub item: usize,
+    pub from: usize,
+    pub to: usize,
+    /// The item's worry level once inspected.
+    pub worry: u64,
+}
+
+/// Plays `rounds` rounds, calling `on_throw` for every item thrown, and returns how many items
+/// each monkey inspected.
+///
+/// A monkey only inspects the items it holds when its turn starts, so one it throws to itself
+/// waits for its next turn instead of being inspected over and over.
+fn play_game(
+    monkeys: &[Monkey],
+    rounds: u32,
+    relief: Relief,
+    mut on_throw: impl FnMut(Throw),
+) -> Result<Vec<u64>, MonkeyError> {
+    let mut held = monkeys
+        .iter()
+        .map(|monkey| monkey.items.iter().copied().collect::<VecDeque<_>>())
+        .collect::<Vec<_>>();
+    let mut items = held
+        .iter()
+        .scan(0, |next, items| {
+            let ids = (*next..*next + items.len()).collect::<VecDeque<_>>();
+            *next += items.len();
+            Some(ids)
+        })
+        .collect::<Vec<_>>();
+    let mut inspections = vec![0; monkeys.len()];
+
+    for round in 1..=rounds {
+        for (index, monkey) in monkeys.iter().enumerate() {
+            let turn = std::mem::take(&mut held[index]);
+            let turn_items = std::mem::take(&mut items[index]);
+
+            for (worry, item) in turn.into_iter().zip(turn_items) {
+                inspections[index] += 1;
+
+                let worry = monkey.inspect(worry, relief)?;
+                let to = monkey.test.target(worry);
+
+                on_throw(Throw {
+                    round,
+                    item,
+                    from: index,
+                    to,
+                    worry,
+                });
+
+                held[to].push_back(worry);
+                items[to].push_back(item);
+            }
+        }
+    }
+
+    Ok(inspections)
+}
+
+/// The product of the two largest numbers of inspections.
+fn monkey_business(inspections: &[u64]) -> u64 {
+    inspections.iter().sorted().rev().take(2).product()
+}
+
+pub fn solution_1(monkeys: &[Monkey]) -> Result<u64, MonkeyError> {
+    play_game(monkeys, 20, Relief::Divide, |_| {}).map(|inspections| monkey_business(&inspections))
+}
+
+/// A record of a game, round by round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    /// For each item, for each round, the monkeys it went through: the one holding it at the start
+    /// of the round, then each one it was thrown to.
+    pub journeys: Vec<Vec<Vec<usize>>>,
+    /// For each round, how many items each monkey inspected.
+    pub inspections: Vec<Vec<u64>>,
+    /// For each round, how many items each monkey held at the end of it.
+    pub held: Vec<Vec<usize>>,
+}
+
+impl Trace {
+    /// A table of the inspections and items held of each monkey, a line per round, ending with
+    /// the total inspections.
+    pub fn report(&self) -> String {
+        let monkeys = self.inspections.first().map_or(0, Vec::len);
+        let mut report = format!(
+            "round{}\n",
+            (0..monkeys)
+                .map(|monkey| format!("  {:>12}", format!("monkey {monkey}")))
+                .collect::<String>()
+        );
+
+        for (round, (inspections, held)) in self.inspections.iter().zip(&self.held).enumerate() {
+            report += &format!("{:>5}", round + 1);
+
+            for (inspections, held) in inspections.iter().zip(held) {
+                report += &format!("  {:>12}", format!("{inspections} ({held})"));
+            }
+
+            report += "\n";
+        }
+
+        report += "total";
+
+        for monkey in 0..monkeys {
+            let total = self
+                .inspections
+                .iter()
+                .map(|round| round[monkey])
+                .sum::<u64>();
+            report += &format!("  {total:>12}");
+        }
+
+        report + "\n"
+    }
+}
+
+/// Builds a [`Trace`] from the throws of a game, in order.
+struct Recorder {
+    trace: Trace,
+    round: u32,
+    /// The monkey holding each item.
+    holders: Vec<usize>,
+    held: Vec<usize>,
+    inspections: Vec<u64>,
+}
+
+impl Recorder {
+    fn new(monkeys: &[Monkey]) -> Self {
+        let holders = monkeys
+            .iter()
+            .enumerate()
+            .flat_map(|(index, monkey)| monkey.items.iter().map(move |_| index))
+            .collect::<Vec<_>>();
+
+        Self {
+            trace: Trace {
+                journeys: vec![vec![]; holders.len()],
+                inspections: vec![],
+                held: vec![],
+            },
+            round: 0,
+            holders,
+            held: monkeys.iter().map(|monkey| monkey.items.len()).collect(),
+            inspections: vec![0; monkeys.len()],
+        }
+    }
+
+    fn end_round(&mut self) {
+        if self.round > 0 {
+            let inspections = vec![0; self.inspections.len()];
+
+            self.trace
+                .inspections
+                .push(std::mem::replace(&mut self.inspections, inspections));
+            self.trace.held.push(self.held.clone());
+        }
+    }
+
+    fn next_round(&mut self) {
+        self.end_round();
+        self.round += 1;
+
+        for (journey, &holder) in self.trace.journeys.iter_mut().zip(&self.holders) {
+            journey.push(vec![holder]);
+        }
+    }
+
+    fn record(&mut self, throw: Throw) {
+        while self.round < throw.round {
+            self.next_round();
+        }
+
+        self.inspections[throw.from] += 1;
+        self.held[throw.from] -= 1;
+        self.held[throw.to] += 1;
+        self.holders[throw.item] = throw.to;
+
+        if let Some(journey) = self.trace.journeys[throw.item].last_mut() {
+            journey.push(throw.to);
+        }
+    }
+
+    /// Fills in the rounds after the last throw, in which nothing happened.
+    fn finish(mut self, rounds: u32) -> Trace {
+        while self.round < rounds {
+            self.next_round();
+        }
+
+        self.end_round();
+        self.trace
+    }
+}
+
+/// Plays `rounds` rounds of the game as in `part`, recording where each item went.
+pub fn trace(monkeys: &[Monkey], rounds: u32, part: Part) -> Result<Trace, MonkeyError> {
+    let relief = Relief::for_part(monkeys, part)?;
+    let mut recorder = Recorder::new(monkeys);
+
+    play_game(monkeys, rounds, relief, |throw| recorder.record(throw))?;
+
+    Ok(recorder.finish(rounds))
+}
+
+/// The smallest modulus worry levels can be kept modulo without changing where items go, as long
+/// as every operation allows it.
+fn modulus(monkeys: &[Monkey]) -> Result<u64, MonkeyError> {
+    let mut moduli = monkeys
+        .iter()
+        .map(|monkey| monkey.test.value)
+        .collect::<Vec<_>>();
+
+    for (index, monkey) in monkeys.iter().enumerate() {
+        if !monkey.operation.ring_moduli(&mut moduli) {
+            return Err(MonkeyError::NotRingCompatible {
+                monkey: index,
+                operation: monkey.operation.to_string(),
+            });
+        }
+    }
+
+    moduli.into_iter().try_fold(1u64, |lcm, modulus| {
+        (lcm / gcd(lcm, modulus))
+            .checked_mul(modulus)
+            .ok_or(MonkeyError::ModulusOverflow)
+    })
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        b => gcd(b, a % b),
+    }
+}
+
+/// Where an item is at the start of a round, when worry levels are kept modulo a modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ItemState {
+    monkey: usize,
+    worry: u64,
+}
+
+/// Items never affect each other, so each can be followed on its own. An item thrown to a later
+/// monkey is inspected again in the same round, and one thrown to an earlier monkey, or back to
+/// the same one, waits for the next round.
+fn item_rounds(
+    monkeys: &[Monkey],
+    modulus: u64,
+    mut state: ItemState,
+    rounds: u64,
+    inspections: &mut [u64],
+) -> ItemState {
+    for _ in 0..rounds {
+        loop {
+            let monkey = &monkeys[state.monkey];
+            inspections[state.monkey] += 1;
+
+            let worry = monkey.operation.eval_modulo(state.worry, modulus);
+            let target = monkey.test.target(worry);
+            let next_round = target <= state.monkey;
+
+            state = ItemState {
+                monkey: target,
+                worry,
+            };
+
+            if next_round {
+                break;
+            }
+        }
+    }
+
+    state
+}
+
+/// Finds after how many rounds an item's state first repeats, and how many rounds apart the
+/// repeats are, with Brent's algorithm. Gives up after `limit` rounds.
+fn find_cycle(
+    monkeys: &[Monkey],
+    modulus: u64,
+    start: ItemState,
+    limit: u64,
+) -> Option<(u64, u64)> {
+    let mut scratch = vec![0; monkeys.len()];
+    let mut step = |state| item_rounds(monkeys, modulus, state, 1, &mut scratch);
+
+    let (mut power, mut length) = (1, 1);
+    let (mut tortoise, mut hare) = (start, step(start));
+    let mut steps = 1;
+
+    while tortoise != hare {
+        if steps > limit {
+            return None;
+        }
+
+        if power == length {
+            tortoise = hare;
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(hare);
+        length += 1;
+        steps += 1;
+    }
+
+    let (mut tortoise, mut hare) = (start, start);
+
+    for _ in 0..length {
+        hare = step(hare);
+    }
+
+    let mut offset = 0;
+
+    while tortoise != hare {
+        tortoise = step(tortoise);
+        hare = step(hare);
+        offset += 1;
+    }
+
+    Some((offset, length))
+}
+
+/// How many items each monkey inspects over `rounds` rounds of part 2's game, following each item
+/// until its state repeats and extrapolating from there, so even 10^12 rounds are quick.
+pub fn inspections(monkeys: &[Monkey], rounds: u64) -> Result<Vec<u64>, MonkeyError> {
+    let modulus = modulus(monkeys)?;
+    let mut totals = vec![0u64; monkeys.len()];
+
+    for (index, monkey) in monkeys.iter().enumerate() {
+        for &worry in &monkey.items {
+            let start = ItemState {
+                monkey: index,
+                worry: worry % modulus,
+            };
+
+            let Some((offset, length)) = find_cycle(monkeys, modulus, start, rounds) else {
+                item_rounds(monkeys, modulus, start, rounds, &mut totals);
+                continue;
+            };
+
+            let cycle_start = item_rounds(monkeys, modulus, start, offset, &mut totals);
+            let mut cycle = vec![0; monkeys.len()];
+            item_rounds(monkeys, modulus, cycle_start, length, &mut cycle);
+
+            let (cycles, rest) = ((rounds - offset) / length, (rounds - offset) % length);
+
+            for (total, per_cycle) in totals.iter_mut().zip(cycle) {
+                *total = per_cycle
+                    .checked_mul(cycles)
+                    .and_then(|inspections| total.checked_add(inspections))
+                    .ok_or(MonkeyError::InspectionOverflow)?;
+            }
+
+            item_rounds(monkeys, modulus, cycle_start, rest, &mut totals);
+        }
+    }
+
+    Ok(totals)
+}
+
+/// Errors with [`MonkeyError::NotRingCompatible`] when an operation divides or subtracts, since
+/// worry levels then cannot be kept small.
+pub fn solution_2(monkeys: &[Monkey], rounds: u64) -> Result<u64, MonkeyError> {
+    inspections(monkeys, rounds).map(|inspections| monkey_business(&inspections))
+}
+
+pub struct Day11;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// How many rounds part 2 plays.
+    pub rounds: u64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { rounds: 10_000 }
+    }
+}
+
+impl aoc_common::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "rounds" => {
+                self.rounds = value
+                    .parse()
+                    .map_err(|_| ParamError::invalid(name, value))?;
+            }
+            _ => return Err(ParamError::Unknown(name.to_string())),
+        }
+
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![("rounds", self.rounds.to_string())]
+    }
+}
+
+impl Solution for Day11 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    type Params = Params;
+    type Input<'a> = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &Params) -> Result<Answer, SolveError> {
+        solution_1(input)
+            .map(Answer::from)
+            .map_err(SolveError::solve)
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Params) -> Result<Answer, SolveError> {
+        solution_2(input, params.rounds)
+            .map(Answer::from)
+            .map_err(SolveError::solve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{example::parse_examples, input_or_skip};
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let example_input = input_or_skip!(crate::inputs().load(11));
+        assert_eq!(Ok(121450), solution_1(&parse(&example_input).unwrap()));
+    }
+
+    #[test]
+    fn parse_reports_bad_notes() {
+        let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old ^ 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3";
+
+        assert_eq!(
+            Err(ParseError::new(
+                3,
+                24,
+                "^ 19",
+                "expected `+`, `-`, `*`, `/` or `%`"
+            )),
+            parse(input).map(|monkeys| monkeys.len())
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 1, "", "expected `Starting items:`")),
+            parse("Monkey 0:\n").map(|monkeys| monkeys.len())
+        );
+    }
+
+    #[test]
+    fn parse_rejects_zero_divisors() {
+        let input = "Monkey 0:
+  Starting items: 79
+  Operation: new = old
+  Test: divisible by 0
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+        assert_eq!(
+            Err(ParseError::new(4, 22, "0", "expected a divisor above 0")),
+            parse(input).map(|monkeys| monkeys.len())
+        );
+    }
+
+    #[test]
+    fn parse_checks_monkey_numbers() {
+        let monkey = |id: usize, target: usize| {
+            format!(
+                "Monkey {id}:
+  Starting items: 1
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey {target}
+    If false: throw to monkey 0"
+            )
+        };
+
+        let monkeys = (0..12)
+            .map(|id| monkey(id, 11 - id))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let parsed = parse(&monkeys).unwrap();
+
+        assert_eq!(12, parsed.len());
+        assert_eq!(11, parsed[0].test.true_target);
+
+        assert_eq!(
+            Err(ParseError::new(8, 8, "2", "expected monkey 1")),
+            parse(&format!("{}\n\n{}", monkey(0, 0), monkey(2, 0))).map(|monkeys| monkeys.len())
+        );
+        assert_eq!(
+            Err(ParseError::new(5, 30, "10", "there is no monkey 10")),
+            parse(&monkey(0, 10)).map(|monkeys| monkeys.len())
+        );
+    }
+
+    #[test]
+    fn parse_expressions() {
+        let operation = |text| parse_all(text, expression);
+
+        assert_eq!(
+            Ok(Expression::binary(
+                Expression::Old,
+                Operator::Add,
+                Expression::binary(Expression::Constant(2), Operator::Multiply, Expression::Old)
+            )),
+            operation("old + 2 * old")
+        );
+
+        for text in [
+            "old * (old - 3) % 7",
+            "(old + 1) * 2 / old",
+            "old - (old - 1)",
+        ] {
+            assert_eq!(Ok(text.to_string()), operation(text).map(|e| e.to_string()));
+        }
+
+        assert_eq!(
+            Err(ParseError::new(1, 9, "", "expected `)`")),
+            operation("(old + 1").map(|e| e.to_string())
+        );
+    }
+
+    #[test]
+    fn eval_checks_arithmetic() {
+        let eval = |text, old| parse_all(text, expression).unwrap().eval(old);
+
+        assert_eq!(Ok(9), eval("(old + 1) * 2 - 3 % 2", 4));
+        assert_eq!(Ok(u64::MAX), eval("old * old / old", u64::MAX));
+        assert_eq!(Err(EvalError::Overflow), eval("old * old", u64::MAX));
+        assert_eq!(Err(EvalError::Underflow), eval("old - 5", 4));
+        assert_eq!(Err(EvalError::DivisionByZero), eval("old / (old - 4)", 4));
+    }
+
+    #[test]
+    fn keep_worry_small_only_when_ring_compatible() {
+        let monkeys = |operation: &str| {
+            parse(&format!(
+                "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = {operation}
+  Test: divisible by 3
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54
+  Operation: new = old * old + 2
+  Test: divisible by 5
+    If true: throw to monkey 0
+    If false: throw to monkey 1"
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(Ok(105), modulus(&monkeys("old % 7 + 1")));
+        assert!(solution_2(&monkeys("old % 7 + 1"), 10_000).is_ok());
+        assert_eq!(
+            Err(MonkeyError::NotRingCompatible {
+                monkey: 0,
+                operation: "old / 2".to_string()
+            }),
+            solution_2(&monkeys("old / 2"), 10_000)
+        );
+        assert_eq!(
+            Err(MonkeyError::NotRingCompatible {
+                monkey: 0,
+                operation: "old * 3 - 1".to_string()
+            }),
+            solution_2(&monkeys("old * 3 - 1"), 10_000)
+        );
+        assert_eq!(
+            Err(SolveError::Solve(
+                "monkey 0's operation `old / 2` cannot be worked out modulo the test divisors"
+                    .to_string()
+            )),
+            Day11::part2(&monkeys("old / 2"), &Params::default())
+        );
+        assert!(matches!(
+            solution_1(&monkeys("old - 100")),
+            Err(MonkeyError::Eval {
+                monkey: 0,
+                err: EvalError::Underflow,
+                ..
+            })
+        ));
+    }
+
+    fn example() -> Vec<Monkey> {
+        let examples = parse_examples(include_str!("examples/day11.toml")).unwrap();
+
+        parse(&examples[0].input).unwrap()
+    }
+
+    #[test]
+    fn trace_items_and_rounds() {
+        let monkeys = example();
+        let trace = trace(&monkeys, 20, Part::One).unwrap();
+
+        assert_eq!(vec![0, 3, 1], trace.journeys[0][0]);
+        assert_eq!(vec![4, 6, 0, 0], trace.held[0]);
+        assert_eq!(20, trace.inspections.len());
+        assert_eq!(
+            "total           101            95             7           105",
+            trace.report().lines().last().unwrap()
+        );
+        assert!(trace
+            .report()
+            .starts_with("round      monkey 0      monkey 1      monkey 2      monkey 3\n    1         2 (4)         4 (6)         3 (0)         5 (0)\n"));
+
+        assert_eq!(
+            vec![2, 4, 3, 6],
+            super::trace(&monkeys, 1, Part::Two).unwrap().inspections[0]
+        );
+    }
+
+    #[test]
+    fn extrapolate_repeating_rounds() {
+        let monkeys = example();
+        let relief = Relief::for_part(&monkeys, Part::Two).unwrap();
+
+        assert_eq!(
+            Ok(vec![52166, 47830, 1938, 52013]),
+            inspections(&monkeys, 10_000)
+        );
+        assert_eq!(
+            play_game(&monkeys, 1234, relief, |_| {}),
+            inspections(&monkeys, 1234)
+        );
+        assert!(inspections(&monkeys, 1_000_000_000_000).unwrap()[0] > 5_000_000_000_000);
+    }
+
+    #[test]
+    fn monkeys_throwing_to_themselves_wait_a_round() {
+        let monkeys = parse(
+            "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 1
+    If false: throw to monkey 0
+
+Monkey 1:
+  Starting items: 54, 65
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 1
+    If false: throw to monkey 0",
+        )
+        .unwrap();
+
+        for part in Part::ALL {
+            let relief = Relief::for_part(&monkeys, part).unwrap();
+            let rounds = play_game(&monkeys, 1, relief, |_| {}).unwrap();
+
+            assert_eq!(vec![2, 2], rounds);
+        }
+
+        let relief = Relief::for_part(&monkeys, Part::Two).unwrap();
+
+        for rounds in [1, 20, 1234] {
+            assert_eq!(
+                play_game(&monkeys, rounds, relief, |_| {}),
+                inspections(&monkeys, rounds as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let example_input = input_or_skip!(crate::inputs().load(11));
+        assert_eq!(
+            Ok(28244037010),
+            solution_2(&parse(&example_input).unwrap(), 10_000)
+        );
+    }
+}
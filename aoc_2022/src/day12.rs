@@ -1,208 +1,303 @@
-use pathfinding::prelude::astar;
-use std::collections::{BTreeMap, BTreeSet};
-
-#[derive(Hash, Eq, PartialEq, Clone, Copy, PartialOrd, Ord, Debug)]
-struct Position(i32, i32);
-
-const LOWEST_ELEVATION: char = 'a';
-const HIGHEST_ELEVATION: char = 'z';
-
-impl Position {
-    fn get_distance(&self, other: &Position) -> u32 {
-        (self.0.abs_diff(other.0)) + (self.1.abs_diff(other.1)) as u32
-    }
-
-    fn neighbours(&self, elevations: &BTreeMap<Position, char>) -> Vec<(Position, u32)> {
-        let current_elevation = elevations.get(self).unwrap().clone();
-        let current_elevation = current_elevation as u8 + 1;
-
-        let neighbors = vec![
-            Position(self.0, self.1 - 1),
-            Position(self.0, self.1 + 1),
-            Position(self.0 - 1, self.1),
-            Position(self.0 + 1, self.1),
-        ];
-
-        let neighbors = neighbors
-            .into_iter()
-            .filter(|neighbor| elevations.contains_key(neighbor))
-            .collect::<Vec<_>>();
-
-        let neighbors = neighbors
-            .into_iter()
-            .filter_map(|neighbor| {
-                let neighbor_elevation = elevations.get(&neighbor).unwrap().clone();
-                let neighbor_elevation = neighbor_elevation as u8;
-
-                if neighbor_elevation > current_elevation {
-                    None
-                } else {
-                    Some((neighbor, 1))
-                }
-            })
-            .collect::<Vec<(Position, u32)>>();
-
-        neighbors
-    }
-}
-
-struct Grid {
-    positions: Vec<BTreeSet<Position>>,
-    elevations: BTreeMap<Position, char>,
-
-    start_pos: Option<Position>,
-    end_pos: Option<Position>,
-}
-
-impl Grid {
-    fn new() -> Self {
-        Self {
-            positions: vec![],
-            elevations: BTreeMap::new(),
-            start_pos: None,
-            end_pos: None,
-        }
-    }
-
-    fn edges(&self) -> Vec<&Position> {
-        let grid_size = self.positions.len();
-        let row_size = self.positions[0].len();
-
-        self.positions
-            .iter()
-            .enumerate()
-            .filter_map(|(index, set)| {
-                if index == 0 || index == grid_size - 1 {
-                    Some(set.into_iter().collect::<Vec<_>>())
-                } else {
-                    Some(
-                        set.into_iter()
-                            .enumerate()
-                            .filter_map(|(row_index, row_item)| {
-                                if row_index == 0 || row_index == row_size - 1 {
-                                    Some(row_item)
-                                } else {
-                                    None
-                                }
-                            })
-                            .collect::<Vec<_>>(),
-                    )
-                }
-            })
-            .flatten()
-            .collect::<Vec<_>>()
-    }
-}
-
-fn parse_input(input: &str) -> Grid {
-    let mut grid = Grid::new();
-
-    for (row, line) in input.trim().lines().enumerate() {
-        let chars = line.trim().chars();
-
-        let mut set = BTreeSet::new();
-
-        for (col, char) in chars.enumerate() {
-            let position = Position(col.try_into().unwrap(), row.try_into().unwrap());
-            grid.elevations.insert(
-                position,
-                match char {
-                    'S' => 'a',
-                    'E' => 'z',
-                    char => char,
-                },
-            );
-
-            match char {
-                'S' => grid.start_pos = Some(position),
-                'E' => grid.end_pos = Some(position),
-                _ => {}
-            }
-
-            set.insert(position);
-        }
-
-        grid.positions.push(set);
-    }
-    grid
-}
-
-fn solution_1(input: &str) -> u32 {
-    let grid = parse_input(input);
-
-    let result = astar(
-        &grid.start_pos.unwrap(),
-        |p| p.neighbours(&grid.elevations),
-        |p| p.get_distance(&grid.end_pos.unwrap()),
-        |p| *p == grid.end_pos.unwrap(),
-    );
-
-    match result {
-        Some(val) => val.1,
-        None => 0,
-    }
-}
-
-fn solution_2(input: &str) -> u32 {
-    let grid = parse_input(input);
-
-    grid.edges()
-        .into_iter()
-        .filter(|item| grid.elevations.get(&item) == Some(&LOWEST_ELEVATION))
-        .filter_map(|pos| {
-            let res = astar(
-                pos,
-                |p| p.neighbours(&grid.elevations),
-                |p| p.get_distance(&grid.end_pos.unwrap()),
-                |p| *p == grid.end_pos.unwrap(),
-            );
-
-            match res {
-                Some(val) => Some(val.1),
-                None => None,
-            }
-        })
-        .min()
-        .unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "Sabqponm
-        abcryxxl
-        accszExk
-        acctuvwj
-        abdefghi";
-
-        assert_eq!(31, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day12.txt");
-
-        assert_eq!(339, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "Sabqponm
-        abcryxxl
-        accszExk
-        acctuvwj
-        abdefghi";
-
-        assert_eq!(29, solution_2(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day12.txt");
-
-        assert_eq!(332, solution_2(input));
-    }
-}
+use std::collections::VecDeque;
+
+use aoc_common::{grid::Position, Answer, Dir4, Grid, ParseError, Solution, SolveError};
+
+const LOWEST_ELEVATION: u8 = b'a';
+const HIGHEST_ELEVATION: u8 = b'z';
+
+pub struct Heightmap {
+    elevations: Grid<u8>,
+
+    start_pos: Position,
+    end_pos: Position,
+}
+
+impl Heightmap {
+    /// Whether a step from `from` to `to` climbs at most one unit.
+    fn can_step(&self, from: Position, to: Position) -> bool {
+        self.elevations[to] <= self.elevations[from] + 1
+    }
+
+    /// The fewest steps from every cell to the end, or `None` for cells that cannot reach it.
+    ///
+    /// This walks back from the end with the climbing rule turned around, so a single search
+    /// covers every start.
+    pub fn distances_to_end(&self) -> Grid<Option<u32>> {
+        let mut distances = Grid::new(self.elevations.width(), self.elevations.height(), None);
+        let mut queue = VecDeque::from([self.end_pos]);
+        distances[self.end_pos] = Some(0);
+
+        while let Some(position) = queue.pop_front() {
+            let distance = distances[position].map(|distance| distance + 1);
+
+            for neighbour in self.elevations.neighbours4(position) {
+                if distances[neighbour].is_none() && self.can_step(neighbour, position) {
+                    distances[neighbour] = distance;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    pub fn start(&self) -> Position {
+        self.start_pos
+    }
+
+    pub fn end(&self) -> Position {
+        self.end_pos
+    }
+
+    /// One of the shortest paths from `start` to the end, both included, or `None` when the end
+    /// cannot be reached. `distances` are those of [`Heightmap::distances_to_end`].
+    pub fn path_from(
+        &self,
+        distances: &Grid<Option<u32>>,
+        start: Position,
+    ) -> Option<Vec<Position>> {
+        let mut path = vec![start];
+        let mut distance = distances[start]?;
+
+        while distance > 0 {
+            let position = *path.last().unwrap();
+
+            // Some neighbour one step closer to the end can always be stepped to.
+            let next = self.elevations.neighbours4(position).find(|&next| {
+                distances[next] == Some(distance - 1) && self.can_step(position, next)
+            })?;
+
+            path.push(next);
+            distance -= 1;
+        }
+
+        Some(path)
+    }
+
+    /// The shortest path from any cell of the lowest elevation, given the
+    /// [`Heightmap::distances_to_end`].
+    pub fn shortest_hike(&self, distances: &Grid<Option<u32>>) -> Option<Vec<Position>> {
+        let start = self
+            .elevations
+            .iter()
+            .filter(|&(_, &elevation)| elevation == LOWEST_ELEVATION)
+            .filter_map(|(position, _)| Some((distances[position]?, position)))
+            .min()?
+            .1;
+
+        self.path_from(distances, start)
+    }
+
+    /// Which way the path leaves each of its cells, except the last.
+    fn arrows(&self, path: &[Position]) -> Grid<Option<Dir4>> {
+        let mut arrows = self.elevations.map(|_| None);
+
+        for step in path.windows(2) {
+            arrows[step[0]] = Dir4::ALL
+                .into_iter()
+                .find(|dir| self.elevations.offset(step[0], dir.offset()) == Some(step[1]));
+        }
+
+        arrows
+    }
+
+    /// Draws the heightmap with `path` marked by arrows, like the puzzle's pictures, but with the
+    /// elevations of the cells off the path instead of dots.
+    pub fn render(&self, path: &[Position]) -> String {
+        self.render_cells(path, |_, char, _| char.to_string())
+    }
+
+    /// Draws the heightmap like [`Heightmap::render`], shading each cell by elevation with ANSI
+    /// colours and picking the path out in bold red.
+    pub fn render_ansi(&self, path: &[Position]) -> String {
+        self.render_cells(path, |position, char, on_path| {
+            // The 24 greys of the 256 colour palette, from dark to light.
+            let grey = 232 + (self.elevations[position] - LOWEST_ELEVATION) as u32 * 23 / 25;
+            let foreground = match (on_path, grey < 244) {
+                (true, _) => "1;38;5;196",
+                (false, true) => "38;5;250",
+                (false, false) => "38;5;236",
+            };
+
+            format!("\x1b[{foreground};48;5;{grey}m{char}\x1b[0m")
+        })
+    }
+
+    /// Draws each cell with `cell`, given its position, its character and whether it is on the
+    /// path.
+    fn render_cells(
+        &self,
+        path: &[Position],
+        mut cell: impl FnMut(Position, char, bool) -> String,
+    ) -> String {
+        let arrows = self.arrows(path);
+        let mut on_path = self.elevations.map(|_| false);
+        let mut text = String::new();
+
+        for &position in path {
+            on_path[position] = true;
+        }
+
+        for y in 0..self.elevations.height() {
+            for x in 0..self.elevations.width() {
+                let position = Position::new(x, y);
+
+                let char = match arrows[position] {
+                    Some(dir) => dir.arrow(),
+                    None if position == self.end_pos => 'E',
+                    None if position == self.start_pos => 'S',
+                    None => self.elevations[position] as char,
+                };
+
+                text += &cell(position, char, on_path[position]);
+            }
+
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+pub fn parse(input: &str) -> Result<Heightmap, ParseError> {
+    let grid = Grid::parse(input, |char| {
+        (char.is_ascii_lowercase() || char == 'S' || char == 'E').then_some(char as u8)
+    })?;
+
+    let find = |marker: u8| {
+        grid.position(|&char| char == marker).ok_or_else(|| {
+            ParseError::end_of(input, format!("expected the `{}` marker", marker as char))
+        })
+    };
+
+    let start_pos = find(b'S')?;
+    let end_pos = find(b'E')?;
+
+    let elevations = grid.map(|&char| match char {
+        b'S' => LOWEST_ELEVATION,
+        b'E' => HIGHEST_ELEVATION,
+        char => char,
+    });
+
+    Ok(Heightmap {
+        elevations,
+        start_pos,
+        end_pos,
+    })
+}
+
+pub fn solution_1(grid: &Heightmap) -> Option<u32> {
+    grid.distances_to_end()[grid.start_pos]
+}
+
+/// The fewest steps to the end from any cell of the lowest elevation.
+pub fn solution_2(grid: &Heightmap) -> Option<u32> {
+    let distances = grid.distances_to_end();
+
+    grid.elevations
+        .iter()
+        .filter(|&(_, &elevation)| elevation == LOWEST_ELEVATION)
+        .filter_map(|(position, _)| distances[position])
+        .min()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+
+    type Params = ();
+    type Input<'a> = Heightmap;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{example::parse_examples, input_or_skip};
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(12));
+
+        assert_eq!(Some(339), solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_requires_start_and_end() {
+        assert_eq!(
+            "expected the `E` marker at line 2, column 4",
+            parse("Sab\nabc").err().unwrap().to_string()
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 2, "A", "invalid cell")),
+            parse("SAE").map(|heightmap| heightmap.start_pos)
+        );
+    }
+
+    #[test]
+    fn search_from_every_low_cell() {
+        // The only `a` that can reach the end is in the middle of the map, away from its edges.
+        let walls = "z".repeat(27);
+        let heightmap = parse(&format!(
+            "S{}\nzabcdefghijklmnopqrstuvwxyE\n{walls}",
+            &walls[1..]
+        ))
+        .unwrap();
+        let distances = heightmap.distances_to_end();
+
+        assert_eq!(None, solution_1(&heightmap));
+        assert_eq!(Some(25), solution_2(&heightmap));
+        assert_eq!(Some(0), distances[heightmap.end_pos]);
+        assert_eq!(Some(1), distances[Position::new(25, 1)]);
+    }
+
+    #[test]
+    fn render_paths() {
+        let examples = parse_examples(include_str!("examples/day12.toml")).unwrap();
+        let heightmap = parse(&examples[0].input).unwrap();
+        let distances = heightmap.distances_to_end();
+        let path = heightmap.path_from(&distances, heightmap.start()).unwrap();
+
+        assert_eq!(32, path.len());
+        assert_eq!(Some(heightmap.end()), path.last().copied());
+        assert!(path
+            .windows(2)
+            .all(|step| step[0].manhattan(step[1]) == 1 && heightmap.can_step(step[0], step[1])));
+
+        let text = heightmap.render(&path);
+        let arrows = text.chars().filter(|char| "^v<>".contains(*char));
+
+        assert_eq!(31, arrows.count());
+        assert_eq!(Some(2), text.lines().position(|line| line.contains('E')));
+
+        let hike = heightmap.shortest_hike(&distances).unwrap();
+        let ansi = heightmap.render_ansi(&hike);
+
+        assert_eq!(30, hike.len());
+        assert!(ansi.starts_with("\x1b[38;5;250;48;5;232mS\x1b[0m"));
+        // The hike starts on an `a`, the darkest grey.
+        assert!(ansi.contains("\x1b[1;38;5;196;48;5;232m"));
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(12));
+
+        assert_eq!(Some(332), solution_2(&parse(&input).unwrap()));
+    }
+}
@@ -1,146 +1,470 @@
-use std::iter::once;
-
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::newline,
-    multi::{separated_list0, separated_list1},
-    sequence::{delimited, separated_pair},
-    *,
-};
-
-#[derive(Debug, Eq, Clone)]
-enum Packet {
-    Nested(Vec<Packet>),
-    Single(u32),
-}
-
-impl PartialEq for Packet {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::Nested(left), Self::Nested(right)) => left == right,
-            (Self::Single(left), Self::Single(right)) => left == right,
-            (Self::Nested(left), Self::Single(right)) => *left == vec![Packet::Single(*right)],
-            (Self::Single(left), Self::Nested(right)) => vec![Packet::Single(*left)] == *right,
-        }
-    }
-}
-
-impl PartialOrd for Packet {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
-    }
-}
-
-impl Ord for Packet {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match (self, other) {
-            (Packet::Nested(left), Packet::Nested(right)) => left.cmp(&right),
-            (Packet::Nested(left), Packet::Single(right)) => {
-                left.cmp(&vec![Packet::Single(*right)])
-            }
-            (Packet::Single(left), Packet::Nested(right)) => {
-                vec![Packet::Single(*left)].cmp(&right)
-            }
-            (Packet::Single(left), Packet::Single(right)) => left.cmp(&right),
-        }
-    }
-}
-
-fn parse_packet(input: &str) -> IResult<&str, Packet> {
-    let nested_parser = delimited(tag("["), separated_list0(tag(","), parse_packet), tag("]"))
-        .map(|vec| Packet::Nested(vec));
-
-    let primitive_parser = nom::character::complete::u32.map(|num| Packet::Single(num));
-
-    let mut parser = alt((nested_parser, primitive_parser));
-
-    parser(input)
-}
-
-fn parse(input: &str) -> IResult<&str, Vec<(Packet, Packet)>> {
-    let newline_tag = tag("\n\n");
-
-    let pairs_parser = separated_pair(parse_packet, newline, parse_packet).map(|(l, r)| (l, r));
-
-    let mut parser = separated_list1(newline_tag, pairs_parser);
-
-    parser(input)
-}
-
-fn solution_1(input: &str) -> usize {
-    let (_, pairs) = parse(input).unwrap();
-
-    pairs
-        .into_iter()
-        .enumerate()
-        .filter(|(_, (l, r))| l.cmp(&r) == std::cmp::Ordering::Less)
-        .map(|(index, _)| index + 1)
-        .sum()
-}
-
-fn solution_2(input: &str) -> usize {
-    let (_, pairs) = parse(input).unwrap();
-
-    let mut flat_mapped = pairs
-        .into_iter()
-        .flat_map(|(left, right)| once(left).chain(once(right)))
-        .collect::<Vec<_>>();
-
-    let two = Packet::Nested(vec![Packet::Nested(vec![Packet::Single(2)])]);
-    let six = Packet::Nested(vec![Packet::Nested(vec![Packet::Single(6)])]);
-
-    flat_mapped.push(two.clone());
-    flat_mapped.push(six.clone());
-
-    flat_mapped.sort();
-
-    let two_index = flat_mapped
-        .iter()
-        .enumerate()
-        .find(|(_, item)| *item == &two)
-        .map(|(index, _)| index + 1)
-        .unwrap();
-
-    let six_index = flat_mapped
-        .iter()
-        .enumerate()
-        .find(|(_, item)| *item == &six)
-        .map(|(index, _)| index + 1)
-        .unwrap();
-
-    two_index * six_index
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = include_str!("input/example/day13.txt");
-
-        assert_eq!(13, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day13.txt");
-
-        assert_eq!(5825, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = include_str!("input/example/day13.txt");
-
-        assert_eq!(140, solution_2(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day13.txt");
-
-        assert_eq!(24477, solution_2(input));
-    }
-}
+use std::{cmp::Ordering, fmt, iter::once, str::FromStr};
+
+use aoc_common::{parse::parse_all, Answer, ParseError, Solution, SolveError};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::newline,
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, separated_pair},
+    *,
+};
+use serde_json::Value;
+
+#[derive(Debug, Eq, Clone)]
+pub enum Packet {
+    Nested(Vec<Packet>),
+    Single(u32),
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Nested(left), Self::Nested(right)) => left == right,
+            (Self::Single(left), Self::Single(right)) => left == right,
+            (Self::Nested(left), Self::Single(right)) => *left == vec![Packet::Single(*right)],
+            (Self::Single(left), Self::Nested(right)) => vec![Packet::Single(*left)] == *right,
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Packet::Nested(left), Packet::Nested(right)) => left.cmp(right),
+            (Packet::Nested(left), Packet::Single(right)) => {
+                left.cmp(&vec![Packet::Single(*right)])
+            }
+            (Packet::Single(left), Packet::Nested(right)) => {
+                vec![Packet::Single(*left)].cmp(right)
+            }
+            (Packet::Single(left), Packet::Single(right)) => left.cmp(right),
+        }
+    }
+}
+
+/// Writes the packet back in the input's syntax.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Single(value) => write!(f, "{value}"),
+            Packet::Nested(packets) => {
+                write!(f, "[")?;
+
+                for (index, packet) in packets.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, "{packet}")?;
+                }
+
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_all(s, parse_packet)
+    }
+}
+
+impl From<&Packet> for Value {
+    fn from(packet: &Packet) -> Self {
+        match packet {
+            Packet::Single(value) => Value::from(*value),
+            Packet::Nested(packets) => Value::Array(packets.iter().map(Value::from).collect()),
+        }
+    }
+}
+
+/// A JSON value that is not a packet, because it is not made of lists and integers that fit a
+/// `u32`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotAPacket(pub Value);
+
+impl fmt::Display for NotAPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a packet", self.0)
+    }
+}
+
+impl std::error::Error for NotAPacket {}
+
+impl TryFrom<&Value> for Packet {
+    type Error = NotAPacket;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Array(values) => values
+                .iter()
+                .map(Packet::try_from)
+                .collect::<Result<_, _>>()
+                .map(Packet::Nested),
+            Value::Number(number) => number
+                .as_u64()
+                .and_then(|number| number.try_into().ok())
+                .map(Packet::Single)
+                .ok_or_else(|| NotAPacket(value.clone())),
+            _ => Err(NotAPacket(value.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Left => write!(f, "left"),
+            Side::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// What settled the comparison of two packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Two different integers.
+    Integers { left: u32, right: u32 },
+    /// The list on one side ran out of items first, and so comes first.
+    RanOut(Side),
+    /// Nothing did: the packets are equal.
+    Equal,
+}
+
+/// An integer compared with a list, and so turned into a list of itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Promotion {
+    /// How many indices into the path the integer was.
+    pub depth: usize,
+    pub side: Side,
+}
+
+/// Why two packets are ordered the way they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub ordering: Ordering,
+    /// The indices of the items compared last, from the outermost list inwards.
+    pub path: Vec<usize>,
+    pub rule: Rule,
+    pub promotions: Vec<Promotion>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let at = |depth: usize| match depth {
+            0 => "the top".to_string(),
+            depth => self.path[..depth]
+                .iter()
+                .map(|index| format!("[{index}]"))
+                .collect(),
+        };
+
+        match self.rule {
+            Rule::Integers { left, right } => {
+                let compared = match left < right {
+                    true => "smaller",
+                    false => "larger",
+                };
+
+                write!(
+                    f,
+                    "at {} the left {left} is {compared} than the right {right}",
+                    at(self.path.len())
+                )?
+            }
+            Rule::RanOut(side) => write!(
+                f,
+                "at {} the {side} list runs out first",
+                at(self.path.len())
+            )?,
+            Rule::Equal => write!(f, "the packets are equal")?,
+        }
+
+        for promotion in &self.promotions {
+            write!(
+                f,
+                ", after turning the {} integer at {} into a list",
+                promotion.side,
+                at(promotion.depth)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares two packets like [`Ord`] does, explaining which items settled it and how.
+pub fn compare_explained(left: &Packet, right: &Packet) -> Explanation {
+    let mut explanation = Explanation {
+        ordering: Ordering::Equal,
+        path: vec![],
+        rule: Rule::Equal,
+        promotions: vec![],
+    };
+
+    explain(left, right, &mut explanation);
+    explanation
+}
+
+/// Fills in `explanation` and returns `true` once the comparison is settled.
+fn explain(left: &Packet, right: &Packet, explanation: &mut Explanation) -> bool {
+    let depth = explanation.path.len();
+
+    let promoted;
+
+    let (left, right): (&[Packet], &[Packet]) = match (left, right) {
+        (Packet::Single(left), Packet::Single(right)) => {
+            if left == right {
+                return false;
+            }
+
+            explanation.ordering = left.cmp(right);
+            explanation.rule = Rule::Integers {
+                left: *left,
+                right: *right,
+            };
+            return true;
+        }
+        (Packet::Nested(left), Packet::Nested(right)) => (left, right),
+        (Packet::Single(left), Packet::Nested(right)) => {
+            explanation.promotions.push(Promotion {
+                depth,
+                side: Side::Left,
+            });
+            promoted = [Packet::Single(*left)];
+            (&promoted, right)
+        }
+        (Packet::Nested(left), Packet::Single(right)) => {
+            explanation.promotions.push(Promotion {
+                depth,
+                side: Side::Right,
+            });
+            promoted = [Packet::Single(*right)];
+            (left, &promoted)
+        }
+    };
+
+    for (index, (left, right)) in left.iter().zip(right).enumerate() {
+        explanation.path.push(index);
+
+        if explain(left, right, explanation) {
+            return true;
+        }
+
+        explanation.path.pop();
+    }
+
+    if left.len() != right.len() {
+        explanation.ordering = left.len().cmp(&right.len());
+        explanation.rule = match explanation.ordering {
+            Ordering::Less => Rule::RanOut(Side::Left),
+            _ => Rule::RanOut(Side::Right),
+        };
+        return true;
+    }
+
+    // Promotions on the way to items that turned out equal did not settle anything.
+    explanation
+        .promotions
+        .retain(|promotion| promotion.depth < depth);
+    false
+}
+
+fn parse_packet(input: &str) -> IResult<&str, Packet> {
+    let nested_parser = delimited(tag("["), separated_list0(tag(","), parse_packet), tag("]"))
+        .map(Packet::Nested);
+
+    let primitive_parser = nom::character::complete::u32.map(Packet::Single);
+
+    let mut parser = alt((nested_parser, primitive_parser));
+
+    parser(input)
+}
+
+fn parse_pairs(input: &str) -> IResult<&str, Vec<(Packet, Packet)>> {
+    let newline_tag = tag("\n\n");
+
+    let pairs_parser = separated_pair(parse_packet, newline, parse_packet).map(|(l, r)| (l, r));
+
+    let mut parser = separated_list1(newline_tag, pairs_parser);
+
+    parser(input)
+}
+
+pub fn parse(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    parse_all(input, parse_pairs)
+}
+
+pub fn solution_1(pairs: &[(Packet, Packet)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter(|(_, (l, r))| l.cmp(r) == std::cmp::Ordering::Less)
+        .map(|(index, _)| index + 1)
+        .sum()
+}
+
+pub fn solution_2(pairs: &[(Packet, Packet)]) -> usize {
+    let mut flat_mapped = pairs
+        .iter()
+        .flat_map(|(left, right)| once(left.clone()).chain(once(right.clone())))
+        .collect::<Vec<_>>();
+
+    let two = Packet::Nested(vec![Packet::Nested(vec![Packet::Single(2)])]);
+    let six = Packet::Nested(vec![Packet::Nested(vec![Packet::Single(6)])]);
+
+    flat_mapped.push(two.clone());
+    flat_mapped.push(six.clone());
+
+    flat_mapped.sort();
+
+    let two_index = flat_mapped
+        .iter()
+        .enumerate()
+        .find(|(_, item)| *item == &two)
+        .map(|(index, _)| index + 1)
+        .unwrap();
+
+    let six_index = flat_mapped
+        .iter()
+        .enumerate()
+        .find(|(_, item)| *item == &six)
+        .map(|(index, _)| index + 1)
+        .unwrap();
+
+    two_index * six_index
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    type Params = ();
+    type Input<'a> = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{example::parse_examples, input_or_skip};
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(13));
+
+        assert_eq!(5825, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_points_at_malformed_packets() {
+        let err = parse("[1,[2]]\n[3]\n\n[1,2\n[4]").err().unwrap();
+
+        assert_eq!((4, 1), (err.line, err.column));
+        assert_eq!("[1,2", err.text);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for text in ["[1,[2,[3,[4,[5,6,7]]]],8,9]", "[[]]", "[]", "[[4,4],4,4,4]"] {
+            let packet = text.parse::<Packet>().unwrap();
+
+            assert_eq!(text, packet.to_string());
+            assert_eq!(text, Value::from(&packet).to_string());
+            assert_eq!(
+                Ok(text.to_string()),
+                Packet::try_from(&Value::from(&packet)).map(|packet| packet.to_string())
+            );
+        }
+
+        let json = serde_json::json!([1, [-2]]);
+
+        assert_eq!(
+            Err(NotAPacket(serde_json::json!(-2))),
+            Packet::try_from(&json)
+        );
+    }
+
+    #[test]
+    fn explain_orderings() {
+        let explain = |left: &str, right: &str| {
+            compare_explained(&left.parse().unwrap(), &right.parse().unwrap())
+        };
+
+        let explanation = explain("[1,1,3,1,1]", "[1,1,5,1,1]");
+        assert_eq!(Ordering::Less, explanation.ordering);
+        assert_eq!(vec![2], explanation.path);
+        assert_eq!(Rule::Integers { left: 3, right: 5 }, explanation.rule);
+
+        let explanation = explain("[[1],[2,3,4]]", "[[1],4]");
+        assert_eq!(vec![1, 0], explanation.path);
+        assert_eq!(
+            vec![Promotion {
+                depth: 1,
+                side: Side::Right
+            }],
+            explanation.promotions
+        );
+        assert_eq!(
+            "at [1][0] the left 2 is smaller than the right 4, after turning the right integer at [1] into a list",
+            explanation.to_string()
+        );
+
+        let explanation = explain("[[[]]]", "[[]]");
+        assert_eq!(Ordering::Greater, explanation.ordering);
+        assert_eq!(Rule::RanOut(Side::Right), explanation.rule);
+        assert_eq!(vec![0], explanation.path);
+
+        let explanation = explain("[3,[4]]", "[[3],[4]]");
+        assert_eq!(Rule::Equal, explanation.rule);
+        assert!(explanation.promotions.is_empty());
+        assert_eq!(Ordering::Equal, explanation.ordering);
+    }
+
+    #[test]
+    fn explanations_agree_with_ord() {
+        let examples = parse_examples(include_str!("examples/day13.toml")).unwrap();
+
+        for (left, right) in parse(&examples[0].input).unwrap() {
+            assert_eq!(left.cmp(&right), compare_explained(&left, &right).ordering);
+        }
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(13));
+
+        assert_eq!(24477, solution_2(&parse(&input).unwrap()));
+    }
+}
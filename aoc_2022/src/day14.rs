@@ -1,183 +1,662 @@
-use std::collections::HashSet;
-
-use itertools::Itertools;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{self, line_ending},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult, Parser,
-};
-
-#[derive(Debug, Clone, Copy, Hash, Eq)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-impl PartialEq for Position {
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
-
-impl Position {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    fn down(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y + 1,
-        }
-    }
-
-    fn left(&self) -> Self {
-        Self {
-            x: self.x - 1,
-            y: self.y + 1,
-        }
-    }
-
-    fn right(&self) -> Self {
-        Self {
-            x: self.x + 1,
-            y: self.y + 1,
-        }
-    }
-}
-
-fn parse_line(input: &str) -> IResult<&str, impl Iterator<Item = Position>> {
-    let input = input.trim();
-    let (input, pairs) = separated_list1(
-        tag(" -> "),
-        separated_pair(complete::i32, tag(","), complete::i32).map(|(x, y)| (x, y)),
-    )(input)?;
-
-    let pairs = pairs.into_iter().tuple_windows().flat_map(|(left, right)| {
-        let (lx, ly) = (left.0, left.1);
-        let (rx, ry) = (right.0, right.1);
-
-        let (min_x, max_x) = (lx.min(rx), lx.max(rx));
-        let (min_y, max_y) = (ly.min(ry), ly.max(ry));
-
-        let x_range = min_x..=max_x;
-        let y_range = min_y..=max_y;
-
-        x_range
-            .cartesian_product(y_range)
-            .map(|(l, r)| Position::new(l, r))
-    });
-
-    Ok((input, pairs))
-}
-
-fn parse(input: &str) -> IResult<&str, HashSet<Position>> {
-    let (input, res) = separated_list1(line_ending, parse_line)(input)?;
-
-    let res = res.into_iter().flatten().collect();
-
-    Ok((input, res))
-}
-
-fn drop_sand(rocks: &HashSet<Position>, max_depth: i32, sand_pos: Position) -> Position {
-    if sand_pos.y == max_depth + 1 {
-        return sand_pos;
-    }
-
-    let down = sand_pos.down();
-    let left = sand_pos.left();
-    let right = sand_pos.right();
-
-    match (
-        rocks.contains(&down),
-        rocks.contains(&left),
-        rocks.contains(&right),
-    ) {
-        (false, _, _) => drop_sand(rocks, max_depth, down),
-        (_, false, _) => drop_sand(rocks, max_depth, left),
-        (_, _, false) => drop_sand(rocks, max_depth, right),
-        _ => sand_pos,
-    }
-}
-
-fn solution_1(input: &str) -> u32 {
-    let (_, mut rocks) = parse(input).unwrap();
-    let rocks_length = rocks.len();
-
-    let Position { y: max_y, .. } = rocks
-        .iter()
-        .max_by(|left, right| left.y.cmp(&right.y))
-        .unwrap()
-        .clone();
-
-    let starting_point = Position::new(500, 0);
-
-    loop {
-        let sand = drop_sand(&rocks, max_y, starting_point);
-        if sand.y == max_y + 1 {
-            break;
-        }
-
-        rocks.insert(sand);
-    }
-
-    (rocks.len() - rocks_length).try_into().unwrap()
-}
-
-fn solution_2(input: &str) -> u32 {
-    let (_, mut rocks) = parse(input).unwrap();
-    let rocks_length = rocks.len();
-
-    let Position { y: max_y, .. } = rocks
-        .iter()
-        .max_by(|left, right| left.y.cmp(&right.y))
-        .unwrap()
-        .clone();
-
-    let starting_point = Position::new(500, 0);
-
-    while !rocks.contains(&starting_point) {
-        let sand = drop_sand(&rocks, max_y, starting_point);
-
-        rocks.insert(sand);
-    }
-
-    (rocks.len() - rocks_length).try_into().unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "498,4 -> 498,6 -> 496,6
-        503,4 -> 502,4 -> 502,9 -> 494,9";
-
-        assert_eq!(24, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day14.txt");
-
-        assert_eq!(1072, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "498,4 -> 498,6 -> 496,6
-        503,4 -> 502,4 -> 502,9 -> 494,9";
-
-        assert_eq!(93, solution_2(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day14.txt");
-
-        assert_eq!(24659, solution_2(input));
-    }
-}
+use std::{fmt, io, path::Path};
+
+use aoc_common::{
+    parse::parse_all, Answer, Dir8, Grid, Image, ParseError, Point2, Solution, SolveError,
+};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{self, line_ending},
+    combinator::verify,
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult, Parser,
+};
+
+type Position = Point2<i32>;
+
+fn parse_line(input: &str) -> IResult<&str, impl Iterator<Item = Position>> {
+    let input = input.trim();
+    let coordinate = || verify(complete::i32, |&value| value >= 0);
+    let (input, pairs) = separated_list1(
+        tag(" -> "),
+        separated_pair(coordinate(), tag(","), coordinate()).map(|(x, y)| (x, y)),
+    )(input)?;
+
+    let pairs = pairs.into_iter().tuple_windows().flat_map(|(left, right)| {
+        let (lx, ly) = (left.0, left.1);
+        let (rx, ry) = (right.0, right.1);
+
+        let (min_x, max_x) = (lx.min(rx), lx.max(rx));
+        let (min_y, max_y) = (ly.min(ry), ly.max(ry));
+
+        let x_range = min_x..=max_x;
+        let y_range = min_y..=max_y;
+
+        x_range
+            .cartesian_product(y_range)
+            .map(|(l, r)| Position::new(l, r))
+    });
+
+    Ok((input, pairs))
+}
+
+fn parse_rocks(input: &str) -> IResult<&str, Vec<Position>> {
+    let (input, res) = separated_list1(line_ending, parse_line)(input)?;
+
+    let res = res.into_iter().flatten().collect();
+
+    Ok((input, res))
+}
+
+const SOURCE: Position = Position::new(500, 0);
+
+/// What is below the lowest rock, two rows down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Floor {
+    /// Nothing: the first grain to fall past the lowest rock keeps falling forever, and the
+    /// pouring stops.
+    Abyss,
+    /// A floor stretching endlessly both ways, so sand piles up until it blocks the sources.
+    Infinite,
+    /// A floor `width` tiles wide starting at column `left`. Sand falling past its ends is lost
+    /// to the abyss.
+    Finite { left: i32, width: u32 },
+}
+
+impl Floor {
+    fn covers(self, x: i32) -> bool {
+        match self {
+            Floor::Abyss => false,
+            Floor::Infinite => true,
+            Floor::Finite { left, width } => (left..left + width as i32).contains(&x),
+        }
+    }
+}
+
+/// The rules sand follows. The default is part 1's: one source at `(500, 0)`, no floor, and
+/// grains falling down, then down and left, then down and right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaveConfig {
+    /// Where grains come from. Each pours one grain in turn, until it is blocked.
+    pub sources: Vec<Position>,
+    pub floor: Floor,
+    /// The moves a grain tries, in order, until one is not blocked. Each must move down.
+    pub offsets: Vec<Position>,
+}
+
+impl Default for CaveConfig {
+    fn default() -> Self {
+        Self {
+            sources: vec![SOURCE],
+            floor: Floor::Abyss,
+            offsets: [Dir8::South, Dir8::SouthWest, Dir8::SouthEast]
+                .map(|dir| dir.offset())
+                .to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// An offset that does not move down, which could leave a grain moving forever.
+    Offset(Position),
+    /// A source that is not between the top of the cave and the floor.
+    Source(Position),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Offset(offset) => write!(f, "offset {offset} does not move down"),
+            ConfigError::Source(source) => {
+                write!(f, "source {source} is not above the floor of the cave")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// The slice of the cave sand can reach, from the top down to just above the floor.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    rocks: Vec<Position>,
+    config: CaveConfig,
+    tiles: Grid<Tile>,
+    /// The x coordinate of the leftmost column of `tiles`.
+    min_x: i32,
+    max_y: i32,
+}
+
+impl Cave {
+    pub fn new(rocks: Vec<Position>, config: CaveConfig) -> Result<Self, ConfigError> {
+        let max_y = rocks.iter().map(|rock| rock.y).max().unwrap_or(0);
+        let floor_y = max_y + 2;
+
+        if let Some(&offset) = config.offsets.iter().find(|offset| offset.y < 1) {
+            return Err(ConfigError::Offset(offset));
+        }
+
+        if let Some(&source) = config
+            .sources
+            .iter()
+            .find(|source| !(0..floor_y).contains(&source.y))
+        {
+            return Err(ConfigError::Source(source));
+        }
+
+        // Sand moves at most the widest offset sideways for every row it falls, and never falls
+        // past the floor.
+        let step = config
+            .offsets
+            .iter()
+            .map(|offset| offset.x.abs())
+            .max()
+            .unwrap_or(0);
+        let reach = |source: &Position| step * (floor_y - source.y);
+        let min_x = rocks
+            .iter()
+            .map(|rock| rock.x)
+            .chain(config.sources.iter().map(|source| source.x - reach(source)))
+            .min()
+            .unwrap_or(0);
+        let max_x = rocks
+            .iter()
+            .map(|rock| rock.x)
+            .chain(config.sources.iter().map(|source| source.x + reach(source)))
+            .max()
+            .unwrap_or(0);
+
+        let mut cave = Self {
+            tiles: Grid::new((max_x - min_x + 1) as usize, floor_y as usize, Tile::Air),
+            rocks,
+            config,
+            min_x,
+            max_y,
+        };
+
+        for &rock in &cave.rocks {
+            let index = cave.index(rock);
+            cave.tiles[index] = Tile::Rock;
+        }
+
+        Ok(cave)
+    }
+
+    /// The same rocks with no sand, following `config` instead.
+    pub fn with_config(&self, config: CaveConfig) -> Result<Self, ConfigError> {
+        Self::new(self.rocks.clone(), config)
+    }
+
+    pub fn config(&self) -> &CaveConfig {
+        &self.config
+    }
+
+    fn floor_y(&self) -> i32 {
+        self.max_y + 2
+    }
+
+    fn index(&self, position: Position) -> Point2<usize> {
+        Point2::new((position.x - self.min_x) as usize, position.y as usize)
+    }
+
+    fn tile(&self, position: Position) -> Option<Tile> {
+        let x = usize::try_from(position.x - self.min_x).ok()?;
+        let y = usize::try_from(position.y).ok()?;
+
+        self.tiles.get(Point2::new(x, y)).copied()
+    }
+
+    fn is_blocked(&self, position: Position) -> bool {
+        match self.tile(position) {
+            Some(tile) => tile != Tile::Air,
+            None => position.y >= self.floor_y() && self.config.floor.covers(position.x),
+        }
+    }
+
+    fn add_sand(&mut self, position: Position) {
+        let index = self.index(position);
+        self.tiles[index] = Tile::Sand;
+    }
+
+    /// The corners of the box around every rock, every grain of sand, the sources and the floor.
+    fn bounds(&self) -> (Position, Position) {
+        let first = self.config.sources.first().copied().unwrap_or(SOURCE);
+        let (mut min, mut max) = (first, first);
+        let mut include = |position: Position| {
+            min = Position::new(min.x.min(position.x), min.y.min(position.y));
+            max = Position::new(max.x.max(position.x), max.y.max(position.y));
+        };
+
+        for (index, &tile) in self.tiles.iter() {
+            if tile != Tile::Air {
+                include(Position::new(index.x as i32 + self.min_x, index.y as i32));
+            }
+        }
+
+        for &source in &self.config.sources {
+            include(source);
+        }
+
+        match self.config.floor {
+            Floor::Abyss => {}
+            Floor::Infinite => max.y = self.floor_y(),
+            Floor::Finite { width: 0, .. } => {}
+            Floor::Finite { left, width } => {
+                include(Position::new(left, self.floor_y()));
+                include(Position::new(left + width as i32 - 1, self.floor_y()));
+            }
+        }
+
+        (min, max)
+    }
+
+    /// Draws the cave within `bounds` as in the puzzle: `#` for rock and the floor, `o` for sand,
+    /// `+` for the sources and `.` for air.
+    fn picture(&self, (min, max): (Position, Position)) -> Grid<char> {
+        let rows = (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| {
+                        let position = Position::new(x, y);
+
+                        match self.tile(position) {
+                            _ if y == self.floor_y() && self.config.floor.covers(x) => '#',
+                            Some(Tile::Rock) => '#',
+                            Some(Tile::Sand) => 'o',
+                            _ if self.config.sources.contains(&position) => '+',
+                            _ => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Grid::from_rows(rows).expect("rows of the same width")
+    }
+
+    /// Draws the cave as text, cropped to what is in it.
+    pub fn render(&self) -> String {
+        self.picture(self.bounds()).to_string()
+    }
+
+    /// Draws the cave like [`Cave::render`], a pixel per tile.
+    pub fn image(&self) -> Image {
+        self.image_within(self.bounds())
+    }
+
+    fn image_within(&self, bounds: (Position, Position)) -> Image {
+        Image::from_grid(&self.picture(bounds), |&tile| match tile {
+            '#' => [110, 100, 95],
+            'o' => [230, 190, 90],
+            '+' => [220, 50, 50],
+            _ => [20, 20, 30],
+        })
+    }
+
+    pub fn sand(&self) -> u32 {
+        self.tiles
+            .values()
+            .filter(|&&tile| tile == Tile::Sand)
+            .count()
+            .try_into()
+            .unwrap()
+    }
+}
+
+enum Fall {
+    Settled(Position),
+    Abyss,
+}
+
+/// Moves the grain at the end of `path` until it settles or falls out of the cave, or returns
+/// `None` if the path is empty because its source is blocked.
+fn fall(cave: &mut Cave, path: &mut Vec<Position>) -> Option<Fall> {
+    while let Some(&position) = path.last() {
+        let next = cave
+            .config
+            .offsets
+            .iter()
+            .map(|&offset| position + offset)
+            .find(|&next| !cave.is_blocked(next));
+
+        match next {
+            Some(next) if next.y >= cave.floor_y() => return Some(Fall::Abyss),
+            Some(next) => path.push(next),
+            None => {
+                cave.add_sand(position);
+                path.pop();
+
+                return Some(Fall::Settled(position));
+            }
+        }
+    }
+
+    None
+}
+
+/// Pours sand from each source in turn until a grain falls into the abyss, or until every source
+/// is blocked.
+///
+/// The path of each source's falling grain is kept as a stack. A grain settles where the previous
+/// one fell through at most, so the next grain picks up from the last position on the path that
+/// is still open instead of falling all the way from the source again. Grains from other sources
+/// can settle on a path too, which cuts it short at that position.
+///
+/// `on_settle` is called with the cave after each grain settles.
+pub fn pour(cave: &mut Cave, mut on_settle: impl FnMut(&Cave)) {
+    let mut paths = cave
+        .config
+        .sources
+        .iter()
+        .map(|&source| match cave.is_blocked(source) {
+            true => vec![],
+            false => vec![source],
+        })
+        .collect_vec();
+
+    while paths.iter().any(|path| !path.is_empty()) {
+        for index in 0..paths.len() {
+            match fall(cave, &mut paths[index]) {
+                None => {}
+                Some(Fall::Abyss) => return,
+                Some(Fall::Settled(position)) => {
+                    for path in &mut paths {
+                        if let Some(at) = path.iter().position(|&step| step == position) {
+                            path.truncate(at);
+                        }
+                    }
+
+                    on_settle(cave);
+                }
+            }
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Cave, ParseError> {
+    let rocks = parse_all(input, parse_rocks)?;
+
+    Ok(Cave::new(rocks, CaveConfig::default()).expect("the default config is valid"))
+}
+
+fn pour_onto(cave: &Cave, floor: Floor) -> u32 {
+    let mut cave = cave.clone();
+    cave.config.floor = floor;
+    pour(&mut cave, |_| {});
+
+    cave.sand()
+}
+
+pub fn solution_1(cave: &Cave) -> u32 {
+    pour_onto(cave, Floor::Abyss)
+}
+
+pub fn solution_2(cave: &Cave) -> u32 {
+    pour_onto(cave, Floor::Infinite)
+}
+
+/// Pours sand as [`pour`] does, saving a picture of the cave into `dir` every `every` grains and
+/// once it is full, `scale` pixels to a tile. The frames are named `frame_00001.png` and so on, or
+/// end in `.ppm` when `ppm` is set, and all show the area the sand ends up covering.
+///
+/// Returns how many frames were saved.
+pub fn save_frames(
+    cave: &Cave,
+    every: u32,
+    scale: usize,
+    dir: &Path,
+    ppm: bool,
+) -> io::Result<usize> {
+    let mut full = cave.clone();
+    pour(&mut full, |_| {});
+    let bounds = full.bounds();
+
+    let extension = match ppm {
+        true => "ppm",
+        false => "png",
+    };
+    let mut frames = 0;
+    let mut save = |cave: &Cave| {
+        frames += 1;
+        cave.image_within(bounds)
+            .scaled(scale)
+            .save(&dir.join(format!("frame_{frames:05}.{extension}")))
+    };
+
+    let mut cave = cave.clone();
+    let mut grains = 0;
+    let mut result = Ok(());
+
+    pour(&mut cave, |cave| {
+        grains += 1;
+
+        if result.is_ok() && grains % every.max(1) == 0 {
+            result = save(cave);
+        }
+    });
+
+    result?;
+
+    if grains % every.max(1) != 0 || grains == 0 {
+        save(&cave)?;
+    }
+
+    Ok(frames)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+
+    type Params = ();
+    type Input<'a> = Cave;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_common::{example::parse_examples, input_or_skip};
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(14));
+
+        assert_eq!(1072, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_negative_coordinates() {
+        let err = parse("498,4 -> 498,6\n503,4 -> 503,-1").err().unwrap();
+
+        assert_eq!(2, err.line);
+    }
+
+    #[test]
+    fn sand_slides_off_a_single_rock() {
+        let cave = parse("500,40 -> 500,40").unwrap();
+
+        assert_eq!(0, solution_1(&cave));
+        assert_eq!(42 * 42 - 1, solution_2(&cave));
+    }
+
+    fn example() -> Cave {
+        let examples = parse_examples(include_str!("examples/day14.toml")).unwrap();
+
+        parse(&examples[0].input).unwrap()
+    }
+
+    #[test]
+    fn render_the_example() {
+        let mut cave = example();
+
+        assert!(cave.render().starts_with("......+...\n..........\n"));
+
+        pour(&mut cave, |_| {});
+
+        assert_eq!(
+            "......+...
+..........
+......o...
+.....ooo..
+....#ooo##
+...o#ooo#.
+..###ooo#.
+....oooo#.
+.o.ooooo#.
+#########.",
+            cave.render()
+        );
+
+        let image = cave.image();
+
+        assert_eq!((10, 10), (image.width(), image.height()));
+        assert_eq!(Some([220, 50, 50]), image.get(Point2::new(6, 0)));
+
+        let mut cave = example()
+            .with_config(CaveConfig {
+                floor: Floor::Infinite,
+                ..CaveConfig::default()
+            })
+            .unwrap();
+        pour(&mut cave, |_| {});
+        let text = cave.render();
+
+        assert!(text.lines().next().unwrap().contains(".o."));
+        assert!(text.lines().last().unwrap().chars().all(|char| char == '#'));
+    }
+
+    #[test]
+    fn save_frames_of_the_fill() {
+        let dir = std::env::temp_dir().join(format!("aoc_day14_frames_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(3, save_frames(&example(), 10, 2, &dir, true).unwrap());
+
+        let last = fs::read(dir.join("frame_00003.ppm")).unwrap();
+        assert!(last.starts_with(b"P6\n20 20\n255\n"));
+        assert_eq!(3, fs::read_dir(&dir).unwrap().count());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn configured(rocks: &str, config: CaveConfig) -> Cave {
+        parse(rocks).unwrap().with_config(config).unwrap()
+    }
+
+    fn poured(mut cave: Cave) -> u32 {
+        pour(&mut cave, |_| {});
+
+        cave.sand()
+    }
+
+    #[test]
+    fn pour_from_several_sources() {
+        let far_apart = configured(
+            "550,3 -> 550,3",
+            CaveConfig {
+                sources: vec![Position::new(500, 0), Position::new(600, 0)],
+                floor: Floor::Infinite,
+                ..CaveConfig::default()
+            },
+        );
+
+        assert_eq!(50, poured(far_apart));
+
+        let twice = configured(
+            &parse_examples(include_str!("examples/day14.toml")).unwrap()[0].input,
+            CaveConfig {
+                sources: vec![SOURCE, SOURCE],
+                floor: Floor::Infinite,
+                ..CaveConfig::default()
+            },
+        );
+
+        assert_eq!(93, poured(twice));
+    }
+
+    #[test]
+    fn sand_falls_off_a_finite_floor() {
+        let floor = |left, width| CaveConfig {
+            floor: Floor::Finite { left, width },
+            ..CaveConfig::default()
+        };
+
+        assert_eq!(0, poured(configured("500,1 -> 500,1", floor(499, 3))));
+
+        let cave = configured("500,1 -> 500,1", floor(498, 5));
+        let mut full = cave.clone();
+        pour(&mut full, |_| {});
+
+        assert_eq!(1, full.sand());
+        assert_eq!("..+..\n..#..\n.o...\n#####", full.render());
+    }
+
+    #[test]
+    fn fall_straight_down() {
+        let cave = configured(
+            "500,5 -> 500,5",
+            CaveConfig {
+                offsets: vec![Position::new(0, 1)],
+                ..CaveConfig::default()
+            },
+        );
+
+        assert_eq!(5, poured(cave));
+    }
+
+    #[test]
+    fn reject_invalid_configs() {
+        let cave = parse("500,5 -> 500,5").unwrap();
+
+        assert_eq!(
+            Err(ConfigError::Offset(Position::new(1, 0))),
+            cave.with_config(CaveConfig {
+                offsets: vec![Position::new(0, 1), Position::new(1, 0)],
+                ..CaveConfig::default()
+            })
+            .map(|cave| cave.sand())
+        );
+        assert_eq!(
+            Err(ConfigError::Source(Position::new(500, 7))),
+            cave.with_config(CaveConfig {
+                sources: vec![Position::new(500, 7)],
+                ..CaveConfig::default()
+            })
+            .map(|cave| cave.sand())
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(14));
+
+        assert_eq!(24659, solution_2(&parse(&input).unwrap()));
+    }
+}
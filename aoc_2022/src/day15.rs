@@ -1,3 +1,7 @@
+use aoc_common::{
+    parse::parse_all, Answer, Image, ParamError, ParseError, Point2, Solution, SolveError,
+};
+use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete::line_ending,
@@ -7,68 +11,83 @@ use nom::{
     IResult,
 };
 
-#[derive(Debug, PartialEq)]
-struct Position {
-    x: i64,
-    y: i64,
-}
-
-impl Position {
-    fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-
-    fn distance_to_point(lhs: &Position, rhs: &Position) -> i64 {
-        (rhs.x.abs_diff(lhs.x) + rhs.y.abs_diff(lhs.y)) as i64
-    }
-}
+type Position = Point2<i64>;
 
 #[derive(Debug)]
-struct Sensor {
+pub struct Sensor {
     beacon: Position,
     position: Position,
     range: i64,
 }
 
 impl Sensor {
-    fn new(sensor_pos: (i64, i64), beacon_pos: (i64, i64)) -> Self {
-        let sensor_pos = Position::new(sensor_pos.0, sensor_pos.1);
-        let beacon_pos = Position::new(beacon_pos.0, beacon_pos.1);
-
+    fn new(sensor_pos: Position, beacon_pos: Position) -> Self {
         Self {
-            range: Position::distance_to_point(&sensor_pos, &beacon_pos),
+            range: sensor_pos.manhattan(beacon_pos),
             position: sensor_pos,
             beacon: beacon_pos,
         }
     }
 
-    fn distance_to_beacon(&self) -> i64 {
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// The closest beacon to the sensor.
+    pub fn beacon(&self) -> Position {
+        self.beacon
+    }
+
+    /// How far the sensor can see, which is as far as its beacon.
+    pub fn distance_to_beacon(&self) -> i64 {
         self.range
     }
 
-    fn in_range_of(&self, other: &Position) -> bool {
-        match self.beacon == *other {
-            true => false,
-            false => {
-                let sensor_to_point_distance = Position::distance_to_point(&self.position, other);
-                let sensor_range = self.distance_to_beacon();
+    pub fn covers(&self, point: Position) -> bool {
+        self.position.manhattan(point) <= self.distance_to_beacon()
+    }
 
-                sensor_range >= sensor_to_point_distance
-            }
+    /// The first and last column of row `y` within range of the sensor, if it reaches the row.
+    fn row_coverage(&self, y: i64) -> Option<(i64, i64)> {
+        let spare = self.distance_to_beacon() - (self.position.y - y).abs();
+
+        (spare >= 0).then(|| (self.position.x - spare, self.position.x + spare))
+    }
+
+    /// The first and last row of column `x` within range of the sensor, if it reaches the column.
+    fn column_coverage(&self, x: i64) -> Option<(i64, i64)> {
+        let spare = self.distance_to_beacon() - (self.position.x - x).abs();
+
+        (spare >= 0).then(|| (self.position.y - spare, self.position.y + spare))
+    }
+}
+
+/// Sorts inclusive intervals and joins the ones that overlap or touch.
+fn merge(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    intervals.sort_unstable();
+
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
+
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
         }
     }
+
+    merged
 }
 
-fn parse_coords(input: &str) -> IResult<&str, (i64, i64)> {
+fn parse_coords(input: &str) -> IResult<&str, Position> {
     let (input, coords) = separated_pair(
         preceded(tag("x="), nom::character::complete::i64),
         tag(", "),
         preceded(tag("y="), nom::character::complete::i64),
     )(input)?;
-    Ok((input, coords))
+    Ok((input, coords.into()))
 }
 
-fn parse(input: &str) -> IResult<&str, Vec<Sensor>> {
+fn parse_sensors(input: &str) -> IResult<&str, Vec<Sensor>> {
     let (input, result) = separated_list1(
         line_ending,
         map(
@@ -84,75 +103,469 @@ fn parse(input: &str) -> IResult<&str, Vec<Sensor>> {
     Ok((input, result))
 }
 
-fn solution_1(input: &str, line: i64) -> usize {
-    let (_, sensors) = parse(input).unwrap();
+/// How many even and how many odd numbers there are in `start..end`.
+fn parities(start: i64, end: i64) -> (u64, u64) {
+    let even = (end + 1).div_euclid(2) - (start + 1).div_euclid(2);
+
+    (even as u64, (end - start - even) as u64)
+}
+
+/// The sensors together, and the area their diamond shaped ranges cover.
+#[derive(Debug)]
+pub struct SensorField {
+    sensors: Vec<Sensor>,
+}
+
+impl SensorField {
+    pub fn new(sensors: Vec<Sensor>) -> Self {
+        Self { sensors }
+    }
+
+    pub fn sensors(&self) -> &[Sensor] {
+        &self.sensors
+    }
+
+    /// The covered stretches of row `y`, as sorted, disjoint, inclusive `(first x, last x)`.
+    pub fn row_intervals(&self, y: i64) -> Vec<(i64, i64)> {
+        merge(
+            self.sensors
+                .iter()
+                .filter_map(|sensor| sensor.row_coverage(y))
+                .collect(),
+        )
+    }
+
+    /// The covered stretches of column `x`, as sorted, disjoint, inclusive `(first y, last y)`.
+    pub fn column_intervals(&self, x: i64) -> Vec<(i64, i64)> {
+        merge(
+            self.sensors
+                .iter()
+                .filter_map(|sensor| sensor.column_coverage(x))
+                .collect(),
+        )
+    }
+
+    pub fn covers(&self, point: Position) -> bool {
+        self.sensors.iter().any(|sensor| sensor.covers(point))
+    }
+
+    /// The sensors whose range includes `point`.
+    pub fn covering(&self, point: Position) -> impl Iterator<Item = &Sensor> {
+        self.sensors
+            .iter()
+            .filter(move |sensor| sensor.covers(point))
+    }
+
+    /// Every point no sensor covers from `min` to `max` inclusive, row by row. Each row is
+    /// scanned through its intervals, but every uncovered point is listed, so this is only quick
+    /// for rectangles that are mostly covered.
+    pub fn uncovered(&self, min: Position, max: Position) -> Vec<Position> {
+        let mut points = vec![];
+
+        for y in min.y..=max.y {
+            let mut x = min.x;
+
+            for (start, end) in self.row_intervals(y) {
+                points.extend((x..start.min(max.x + 1)).map(|x| Position::new(x, y)));
+                x = x.max(end + 1);
+            }
+
+            points.extend((x..=max.x).map(|x| Position::new(x, y)));
+        }
+
+        points
+    }
+
+    /// How many points at least one sensor covers.
+    ///
+    /// Turned by 45 degrees, to `u = x + y` and `v = x - y`, each range is a square. The squares'
+    /// edges split the plane into cells that are either covered or not, and the points in a cell
+    /// are those where `u` and `v` are both even or both odd.
+    pub fn covered_area(&self) -> u64 {
+        let squares = self
+            .sensors
+            .iter()
+            .map(|sensor| {
+                let (x, y, range) = (sensor.position.x, sensor.position.y, sensor.range);
+                let (u, v) = (x + y, x - y);
+
+                [(u - range, u + range + 1), (v - range, v + range + 1)]
+            })
+            .collect_vec();
+
+        let edges = |axis: usize| {
+            squares
+                .iter()
+                .flat_map(|square| {
+                    let (start, end) = square[axis];
+                    [start, end]
+                })
+                .sorted_unstable()
+                .dedup()
+                .collect_vec()
+        };
+        let us = edges(0);
+        let vs = edges(1);
+
+        us.iter()
+            .tuple_windows()
+            .cartesian_product(vs.iter().tuple_windows())
+            .filter(|((&u, _), (&v, _))| {
+                squares
+                    .iter()
+                    .any(|[(u0, u1), (v0, v1)]| (*u0..*u1).contains(&u) && (*v0..*v1).contains(&v))
+            })
+            .map(|((&u0, &u1), (&v0, &v1))| {
+                let (even_u, odd_u) = parities(u0, u1);
+                let (even_v, odd_v) = parities(v0, v1);
+
+                even_u * even_v + odd_u * odd_v
+            })
+            .sum()
+    }
+
+    /// The corners of the box around every sensor's range.
+    fn bounds(&self) -> (Position, Position) {
+        let corner = |pick: fn(i64, i64) -> i64, sign: i64| {
+            self.sensors
+                .iter()
+                .map(|sensor| {
+                    let reach = sign * sensor.range;
+                    Position::new(sensor.position.x + reach, sensor.position.y + reach)
+                })
+                .reduce(|a, b| Position::new(pick(a.x, b.x), pick(a.y, b.y)))
+                .unwrap_or_default()
+        };
+
+        (corner(i64::min, -1), corner(i64::max, 1))
+    }
+
+    /// Draws every sensor's range over the area they cover, at most `size` pixels across. The
+    /// more sensors cover a pixel the brighter it is, each range is outlined, and sensors and
+    /// beacons are marked in red and blue.
+    pub fn image(&self, size: usize) -> Image {
+        let (min, max) = self.bounds();
+        let span = (max.x - min.x).max(max.y - min.y) + 1;
+        let step = (span + size.max(1) as i64 - 1) / size.max(1) as i64;
+        let pixel = |point: Position| {
+            let offset = point - min;
+            Point2::new((offset.x / step) as usize, (offset.y / step) as usize)
+        };
+
+        let cells = Point2::new((max.x - min.x) / step + 1, (max.y - min.y) / step + 1);
+        let mut image = Image::new(cells.x as usize, cells.y as usize, [10, 10, 20]);
+
+        for y in 0..cells.y {
+            for x in 0..cells.x {
+                let point = min + Position::new(x, y) * step + Position::new(step / 2, step / 2);
+                let count = self.covering(point).count().min(4) as u8;
+
+                if count > 0 {
+                    image.set(
+                        pixel(point),
+                        [20 + 25 * count, 60 + 35 * count, 40 + 20 * count],
+                    );
+                }
+            }
+        }
 
-    let min_x = sensors
+        for sensor in &self.sensors {
+            let (centre, range) = (sensor.position, sensor.range);
+            let corners = [
+                Position::new(0, -range),
+                Position::new(range, 0),
+                Position::new(0, range),
+                Position::new(-range, 0),
+                Position::new(0, -range),
+            ];
+
+            for (from, to) in corners.into_iter().tuple_windows() {
+                let (from, to) = (pixel(centre + from), pixel(centre + to));
+                let steps = from.x.abs_diff(to.x).max(from.y.abs_diff(to.y));
+
+                for index in 0..=steps {
+                    let along = |a: usize, b: usize| match steps {
+                        0 => a,
+                        _ => {
+                            (a as i64 + (b as i64 - a as i64) * index as i64 / steps as i64)
+                                as usize
+                        }
+                    };
+
+                    image.set(
+                        Point2::new(along(from.x, to.x), along(from.y, to.y)),
+                        [240, 240, 240],
+                    );
+                }
+            }
+        }
+
+        for sensor in &self.sensors {
+            image.set(pixel(sensor.position), [230, 40, 40]);
+            image.set(pixel(sensor.beacon), [60, 120, 255]);
+        }
+
+        image
+    }
+}
+
+pub fn parse(input: &str) -> Result<SensorField, ParseError> {
+    parse_all(input, parse_sensors).map(SensorField::new)
+}
+
+pub fn solution_1(field: &SensorField, line: i64) -> usize {
+    let covered = field.row_intervals(line);
+
+    // Every beacon is within range of its own sensor, so those on the row are all covered.
+    let beacons = field
+        .sensors
         .iter()
-        .map(|sensor| sensor.position.x - sensor.distance_to_beacon())
-        .min()
-        .unwrap();
+        .map(|sensor| sensor.beacon)
+        .filter(|beacon| beacon.y == line)
+        .unique()
+        .count();
 
-    let max_x = sensors
+    covered
         .iter()
-        .map(|sensor| sensor.position.x + sensor.distance_to_beacon())
-        .max()
-        .unwrap();
+        .map(|(start, end)| (end - start + 1) as usize)
+        .sum::<usize>()
+        - beacons
+}
 
-    (min_x..=max_x)
-        .filter(|&x_pos| {
-            sensors
-                .iter()
-                .any(|sensor| sensor.in_range_of(&Position::new(x_pos, line)))
+/// The one position in `0..=limit` on both axes that no sensor covers must sit just outside some
+/// sensors' ranges, so it is where two of the lines bordering their diamonds cross, or where one
+/// meets the edge of the box, or in a corner of it.
+pub fn solution_2(field: &SensorField, limit: i64) -> Option<i64> {
+    // The borders are the lines x + y = sum and x - y = difference.
+    let border = |offset: fn(&Position) -> i64| {
+        field
+            .sensors
+            .iter()
+            .flat_map(move |sensor| {
+                let (centre, distance) = (offset(&sensor.position), sensor.range + 1);
+                [centre - distance, centre + distance]
+            })
+            .collect_vec()
+    };
+    let sums = border(|position| position.x + position.y);
+    let differences = border(|position| position.x - position.y);
+
+    let crossings = sums
+        .iter()
+        .cartesian_product(&differences)
+        .filter(|(sum, difference)| (*sum + *difference) % 2 == 0)
+        .map(|(sum, difference)| Position::new((sum + difference) / 2, (sum - difference) / 2));
+    let edges = sums
+        .iter()
+        .flat_map(|&sum| {
+            [
+                (0, sum),
+                (limit, sum - limit),
+                (sum, 0),
+                (sum - limit, limit),
+            ]
         })
-        .count()
+        .chain(differences.iter().flat_map(|&difference| {
+            [
+                (0, -difference),
+                (limit, limit - difference),
+                (difference, 0),
+                (difference + limit, limit),
+            ]
+        }))
+        .chain([(0, 0), (0, limit), (limit, 0), (limit, limit)])
+        .map(Position::from);
+
+    crossings
+        .chain(edges)
+        .filter(|position| (0..=limit).contains(&position.x) && (0..=limit).contains(&position.y))
+        .find(|&position| !field.covers(position))
+        .map(|position| position.x * 4_000_000 + position.y)
 }
 
-fn solution_2(input: &str, line: i64) -> Option<i64> {
-    let (_, sensors) = parse(input).unwrap();
+pub struct Day15;
 
-    sensors.iter().find_map(|sensor| {
-        let x_pos = (sensor.position.x - (sensor.distance_to_beacon() + 1)).max(0);
-        (x_pos..=sensor.position.x)
-            .zip(sensor.position.y..=line)
-            .find(|pos| {
-                sensors
-                    .iter()
-                    .all(|sensor| !sensor.in_range_of(&Position::new(pos.0, pos.1)))
-            })
-            .map(|(x, y)| x * 4000000 + y)
-    })
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    /// The row scanned for positions that cannot hold a beacon in part 1.
+    pub row: i64,
+    /// The largest x and y coordinate the distress beacon can have in part 2.
+    pub limit: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            row: 2_000_000,
+            limit: 4_000_000,
+        }
+    }
+}
+
+impl aoc_common::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        let field = match name {
+            "row" => &mut self.row,
+            "limit" => &mut self.limit,
+            _ => return Err(ParamError::Unknown(name.to_string())),
+        };
+
+        *field = value
+            .parse()
+            .map_err(|_| ParamError::invalid(name, value))?;
+
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("row", self.row.to_string()),
+            ("limit", self.limit.to_string()),
+        ]
+    }
+}
+
+impl Solution for Day15 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+
+    type Params = Params;
+    type Input<'a> = SensorField;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Params) -> Result<Answer, SolveError> {
+        Ok(solution_1(input, params.row).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Params) -> Result<Answer, SolveError> {
+        Ok(solution_2(input, params.limit).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::{example::parse_examples, input_or_skip};
+
     use super::*;
 
     #[test]
-    fn solution_1_example() {
-        let input = include_str!("input/example/day15.txt");
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(15));
 
-        assert_eq!(26, solution_1(input, 10));
+        assert_eq!(4724228, solution_1(&parse(&input).unwrap(), 2000000));
     }
 
     #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day15.txt");
+    fn parse_reports_trailing_input() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+        Sensor at x=9, y=16: closest beacon at x=10, y=16";
 
-        assert_eq!(4724228, solution_1(input, 2000000));
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                9,
+                "Sensor at x=9, y=16: closest beacon at x=10, y=16",
+                "unexpected trailing input"
+            )),
+            parse(input).map(|field| field.sensors().len())
+        );
+    }
+
+    #[test]
+    fn merge_overlapping_and_touching_intervals() {
+        assert_eq!(
+            vec![(0, 7), (10, 12)],
+            merge(vec![(5, 7), (10, 12), (0, 2), (3, 4), (11, 11)])
+        );
     }
 
     #[test]
-    fn solution_2_example() {
-        let input = include_str!("input/example/day15.txt");
+    fn find_a_beacon_in_a_corner() {
+        let sensors = parse("Sensor at x=0, y=0: closest beacon is at x=4, y=3").unwrap();
+
+        assert_eq!(Some(4 * 4_000_000 + 4), solution_2(&sensors, 4));
+        assert_eq!(None, solution_2(&sensors, 3));
+    }
+
+    fn example() -> SensorField {
+        let examples = parse_examples(include_str!("examples/day15.toml")).unwrap();
+
+        parse(&examples[0].input).unwrap()
+    }
+
+    #[test]
+    fn intervals_on_rows_and_columns() {
+        let field = example();
+
+        assert_eq!(vec![(-2, 24)], field.row_intervals(10));
+        assert_eq!(vec![(-3, 13), (15, 25)], field.row_intervals(11));
+        assert_eq!(vec![(2, 2)], field.row_intervals(-10));
+        assert_eq!(Vec::<(i64, i64)>::new(), field.row_intervals(-11));
+        assert_eq!(vec![(0, 10), (12, 23)], field.column_intervals(14));
+    }
+
+    #[test]
+    fn points_and_their_sensors() {
+        let field = example();
+        let distress = Position::new(14, 11);
+
+        assert!(!field.covers(distress));
+        assert_eq!(
+            vec![Position::new(14, 11)],
+            field.uncovered(Position::new(0, 0), Position::new(20, 20))
+        );
+        assert_eq!(
+            vec![Position::new(8, 7), Position::new(2, 0)],
+            field
+                .covering(Position::new(8, -2))
+                .map(Sensor::position)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(4, field.covering(Position::new(10, 16)).count());
+    }
+
+    #[test]
+    fn covered_area_counts_each_point_once() {
+        let field = example();
+        let (min, max) = field.bounds();
+        let area = (min.y..=max.y)
+            .flat_map(|y| field.row_intervals(y))
+            .map(|(start, end)| (end - start + 1) as u64)
+            .sum::<u64>();
+
+        assert_eq!(area, field.covered_area());
+
+        let single = parse("Sensor at x=3, y=-4: closest beacon is at x=6, y=-5").unwrap();
+        assert_eq!(2 * 4 * 4 + 2 * 4 + 1, single.covered_area());
+    }
+
+    #[test]
+    fn draw_the_coverage_map() {
+        let field = example();
+        let image = field.image(20);
+        let (min, _) = field.bounds();
+
+        assert_eq!((19, 19), (image.width(), image.height()));
+        assert_eq!(Some([10, 10, 20]), image.get(Point2::new(0, 0)));
 
-        assert_eq!(56000011, solution_2(input, 20).unwrap());
+        let sensor = Position::new(8, 7) - min;
+        assert_eq!(
+            Some([230, 40, 40]),
+            image.get(Point2::new(sensor.x as usize / 3, sensor.y as usize / 3))
+        );
     }
 
     #[test]
     fn solution_2_input() {
-        let input = include_str!("input/day15.txt");
+        let input = input_or_skip!(crate::inputs().load(15));
 
-        assert_eq!(13622251246513, solution_2(input, 4000000).unwrap());
+        assert_eq!(
+            13622251246513,
+            solution_2(&parse(&input).unwrap(), 4000000).unwrap()
+        );
     }
 }
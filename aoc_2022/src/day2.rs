@@ -1,63 +1,104 @@
-fn input_to_bytes(input: &str) -> impl Iterator<Item = (u8, u8)> + '_ {
-    input.trim().lines().filter_map(|line| {
-        let mut split = line.split_whitespace();
-
-        Some((
-            split.next()?.as_bytes().first()? - (b'A' - 1),
-            split.next()?.as_bytes().first()? - (b'X' - 1),
-        ))
-    })
-}
-
-fn solution_1(input: &str) -> u32 {
-    input_to_bytes(input)
-        .map(|(left, right)| get_score(left, right))
-        .sum::<u32>()
-}
-
-fn solution_2(input: &str) -> u32 {
-    input_to_bytes(input)
-        .map(|(left, right)| get_score(left, 1 + (right + left) % 3))
-        .sum::<u32>()
-}
-
-fn get_score(left: u8, right: u8) -> u32 {
-    (((4 + right - left) % 3 * 3) + right) as u32
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "A Y
-        B X
-        C Z";
-
-        assert_eq!(15, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day2.txt");
-
-        assert_eq!(15523, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "A Y
-        B X
-        C Z";
-
-        assert_eq!(12, solution_2(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day2.txt");
-
-        assert_eq!(15702, solution_2(input));
-    }
-}
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+
+/// Parses `text`, a slice of `input`, as one of the three letters from `first`, numbered from 1.
+fn parse_shape(input: &str, text: &str, first: u8) -> Result<u8, ParseError> {
+    match text.as_bytes() {
+        &[letter] if (first..first + 3).contains(&letter) => Ok(letter - (first - 1)),
+        _ => Err(ParseError::at(
+            input,
+            text,
+            format!(
+                "expected one of `{}`",
+                (first..first + 3).map(char::from).collect::<String>()
+            ),
+        )),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<(u8, u8)>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+
+            match line.split_once(' ') {
+                Some((opponent, response)) => Ok((
+                    parse_shape(input, opponent, b'A')?,
+                    parse_shape(input, response, b'X')?,
+                )),
+                None => Err(ParseError::at(input, line, "expected two letters")),
+            }
+        })
+        .collect()
+}
+
+pub fn solution_1(rounds: &[(u8, u8)]) -> u32 {
+    rounds
+        .iter()
+        .map(|&(left, right)| get_score(left, right))
+        .sum::<u32>()
+}
+
+pub fn solution_2(rounds: &[(u8, u8)]) -> u32 {
+    rounds
+        .iter()
+        .map(|&(left, right)| get_score(left, 1 + (right + left) % 3))
+        .sum::<u32>()
+}
+
+fn get_score(left: u8, right: u8) -> u32 {
+    (((4 + right - left) % 3 * 3) + right) as u32
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Params = ();
+    type Input<'a> = Vec<(u8, u8)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(2));
+
+        assert_eq!(15523, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_shapes() {
+        assert_eq!(
+            Err(ParseError::new(2, 3, "W", "expected one of `XYZ`")),
+            parse("A Y\nB W")
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(2));
+
+        assert_eq!(15702, solution_2(&parse(&input).unwrap()));
+    }
+}
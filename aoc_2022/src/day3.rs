@@ -1,71 +1,113 @@
-pub fn solution_1(input: &str) -> u32 {
-    let solution: u32 = input
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    input
         .trim()
         .lines()
         .map(|line| line.trim()) // Trim the input lines to remove whitespace
+        .map(
+            |line| match line.find(|item: char| !item.is_ascii_alphabetic()) {
+                Some(index) => Err(ParseError::at(
+                    input,
+                    &line[index..],
+                    "expected an item letter",
+                )),
+                None => Ok(line),
+            },
+        )
+        .collect()
+}
+
+pub fn solution_1(rucksacks: &[&str]) -> u32 {
+    let solution: u32 = rucksacks
+        .iter()
         .map(|line| line.split_at(line.len() / 2)) // Split the input in half
         .flat_map(|(left, right)| left.chars().find(|&y| right.contains(y))) // Find chars that intersect both left and right
-        .filter_map(|input| match input.is_uppercase() {
-            true => Some(input as u32 - 65 + 27), // If it's uppercase, get the byte representation + 27 (a-zA-Z, where A is 26, + 1 for the offset)
-            false => Some(input as u32 - 97 + 1) // If it's lowercase, get the byte representation + 1 for the offset
+        .map(|input| match input.is_uppercase() {
+            true => input as u32 - 65 + 27, // If it's uppercase, get the byte representation + 27 (a-zA-Z, where A is 26, + 1 for the offset)
+            false => input as u32 - 97 + 1 // If it's lowercase, get the byte representation + 1 for the offset
         })
         .sum(); 
     
     solution
 }
 
-pub fn solution_2(input: &str) -> u32 {
-    let solution = input
-        .trim()
-        .lines()
-        .collect::<Vec<_>>()
-        .chunks(3)
+/// Sums the badges of each group of three rucksacks, failing when the rucksacks don't split into
+/// whole groups.
+pub fn solution_2(rucksacks: &[&str]) -> Result<u32, String> {
+    let groups = rucksacks.chunks_exact(3);
+
+    if !groups.remainder().is_empty() {
+        return Err(format!(
+            "expected groups of three rucksacks, but the last group has {}",
+            groups.remainder().len()
+        ));
+    }
+
+    let solution = groups
         .flat_map(|groups| groups[0].chars().find(|&x| groups[1].contains(x) && groups[2].contains(x)))
-        .filter_map(|input| match input.is_uppercase() {
-            true => Some(input as u32 - 65 + 27),
-            false => Some(input as u32 - 97 + 1)
+        .map(|input| match input.is_uppercase() {
+            true => input as u32 - 65 + 27,
+            false => input as u32 - 97 + 1
         })
         .sum(); 
 
-    solution
+    Ok(solution)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Params = ();
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        solution_2(input)
+            .map(Answer::from)
+            .map_err(SolveError::solve)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::input_or_skip;
 
-    #[test]
-    fn solution_1_example() {
-        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
-        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-        PmmdzqPrVvPwwTWBwg
-        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-        ttgJtRGJQctTZtZT
-        CrZsJsPPZsGzwwsLwLmpwMDw";
-
-        assert_eq!(157, solution_1(input))
-    }
+    use super::*;
 
     #[test]
     fn solution_1_input() {
-        let input = include_str!("input/day3.txt");
+        let input = input_or_skip!(crate::inputs().load(3));
 
-        assert_eq!(7701, solution_1(input));
+        assert_eq!(7701, solution_1(&parse(&input).unwrap()));
     }
 
     #[test]
-    fn solution_2_example() {
-        let input = "vJrwpWtwJgWrhcsFMMfFFhFp
-        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-        PmmdzqPrVvPwwTWBwg";
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(3));
 
-        assert_eq!(18, solution_2(input));
+        assert_eq!(Ok(2644), solution_2(&parse(&input).unwrap()));
     }
 
     #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day3.txt");
+    fn solution_2_needs_whole_groups() {
+        let rucksacks = parse("ab\nac\nad\nbb").unwrap();
 
-        assert_eq!(2644, solution_2(input));
+        assert_eq!(
+            Err("expected groups of three rucksacks, but the last group has 1".to_string()),
+            solution_2(&rucksacks)
+        );
+        assert_eq!(Ok(1), solution_2(&rucksacks[..3]));
     }
 }
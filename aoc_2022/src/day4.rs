@@ -1,84 +1,108 @@
-fn solution_1(input: &str) -> u32 {
-    let pairs = input
-        .trim()
-        .lines()
-        .map(|line| line.trim().split(',').collect::<Vec<&str>>())
-        .collect::<Vec<Vec<&str>>>();
-
-    let mut ans: u32 = 0;
-    for pair in pairs {
-        let (first, second) = pair[0].split_once('-').unwrap();
-        let (third, fourth) = pair[1].split_once('-').unwrap();
-
-        let (s1, e1, s2, e2) = (
-            first.parse::<u32>().unwrap(),
-            second.parse::<u32>().unwrap(),
-            third.parse::<u32>().unwrap(),
-            fourth.parse::<u32>().unwrap(),
-        );
-
-        if s1 <= s2 && e2 <= e1 || s2 <= s1 && e1 <= e2 {
-            ans += 1;
-        }
-    }
-
-    ans
-}
-
-fn solution_2(input: &str) -> u32 {
-    let pairs = input
-        .trim()
-        .lines()
-        .map(|line| line.trim().split(',').collect::<Vec<&str>>())
-        .collect::<Vec<Vec<&str>>>();
-
-    let mut ans: u32 = 0;
-    for pair in pairs {
-        let (first, second) = pair[0].split_once('-').unwrap();
-        let (third, fourth) = pair[1].split_once('-').unwrap();
-
-        let (s1, e1, s2, e2) = (
-            first.parse::<u32>().unwrap(),
-            second.parse::<u32>().unwrap(),
-            third.parse::<u32>().unwrap(),
-            fourth.parse::<u32>().unwrap(),
-        );
-
-        if e1 >= s2 && e2 >= s1 {
-            ans += 1;
-        }
-    }
-
-    ans
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "2-4,6-8
-        2-3,4-5
-        5-7,7-9
-        2-8,3-7
-        6-6,4-6
-        2-6,4-8";
-
-        assert_eq!(2, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day4.txt");
-
-        assert_eq!(651, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day4.txt");
-
-        assert_eq!(956, solution_2(input));
-    }
-}
+use aoc_common::{parse::parse_number, Answer, ParseError, Solution, SolveError};
+
+pub type Assignment = (u32, u32);
+
+/// Parses `text`, a slice of `input`, as a range of sections such as `2-4`.
+fn parse_assignment(input: &str, text: &str) -> Result<Assignment, ParseError> {
+    let (start, end) = text
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(input, text, "expected a range like `2-4`"))?;
+
+    Ok((parse_number(input, start)?, parse_number(input, end)?))
+}
+
+pub fn parse(input: &str) -> Result<Vec<(Assignment, Assignment)>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let (first, second) = line
+                .split_once(',')
+                .ok_or_else(|| ParseError::at(input, line, "expected two ranges"))?;
+
+            Ok((
+                parse_assignment(input, first)?,
+                parse_assignment(input, second)?,
+            ))
+        })
+        .collect()
+}
+
+pub fn solution_1(pairs: &[(Assignment, Assignment)]) -> u32 {
+    let mut ans: u32 = 0;
+    for &((s1, e1), (s2, e2)) in pairs {
+        if s1 <= s2 && e2 <= e1 || s2 <= s1 && e1 <= e2 {
+            ans += 1;
+        }
+    }
+
+    ans
+}
+
+pub fn solution_2(pairs: &[(Assignment, Assignment)]) -> u32 {
+    let mut ans: u32 = 0;
+    for &((s1, e1), (s2, e2)) in pairs {
+        if e1 >= s2 && e2 >= s1 {
+            ans += 1;
+        }
+    }
+
+    ans
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Params = ();
+    type Input<'a> = Vec<(Assignment, Assignment)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(4));
+
+        assert_eq!(651, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_reports_bad_ranges() {
+        assert_eq!(
+            Err(ParseError::new(2, 5, "x", "expected a number")),
+            parse("2-4,6-8\n2-3,x-5")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "2-4", "expected two ranges")),
+            parse("2-4")
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(4));
+
+        assert_eq!(956, solution_2(&parse(&input).unwrap()));
+    }
+}
@@ -1,151 +1,407 @@
-use regex::Regex;
-
-// To be refactored
-
-fn solution_1(input: &Vec<Vec<&str>>, instructions: &str) -> String {
-    let mut input = input.to_vec();
-
-    let instructions = instructions
-        .trim()
-        .lines()
-        .map(|instruction| instruction.trim())
-        .collect::<Vec<&str>>();
-
-    let re = Regex::new("move ([0-9]+) from ([0-9]+) to ([0-9]+)").unwrap();
-
-    for instruction in instructions {
-        let matches = re.captures(instruction.trim()).unwrap();
-        let (quantity, source, destination) = (
-            matches.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-            matches.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-            matches.get(3).unwrap().as_str().parse::<usize>().unwrap(),
-        );
-
-        let source = input.get_mut(source - 1).unwrap();
-        let mut removed_items = source.drain(..quantity).collect::<Vec<&str>>();
-
-        let destination = input.get_mut(destination - 1).unwrap();
-
-        destination.reverse();
-        destination.append(&mut removed_items);
-        destination.reverse();
-    }
-
-    let res = input
-        .into_iter()
-        .map(|value| value.iter().next().unwrap().to_string())
-        .collect::<Vec<String>>()
-        .join("");
-
-    res
-}
-
-fn solution_2(input: &Vec<Vec<&str>>, instructions: &str) -> String {
-    let mut input = input.to_vec();
-
-    let instructions = instructions
-        .trim()
-        .lines()
-        .map(|instruction| instruction.trim())
-        .collect::<Vec<&str>>();
-
-    let re = Regex::new("move ([0-9]+) from ([0-9]+) to ([0-9]+)").unwrap();
-
-    let index = 0;
-    for instruction in instructions {
-        let matches = re.captures(instruction.trim()).unwrap();
-        let (quantity, source, destination) = (
-            matches.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-            matches.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-            matches.get(3).unwrap().as_str().parse::<usize>().unwrap(),
-        );
-
-        let source = input.get_mut(source - 1).unwrap();
-        let mut removed_items = source.drain(..quantity).collect::<Vec<&str>>();
-
-        if index % 1 == 0 {
-            removed_items.reverse();
-        }
-
-        let destination = input.get_mut(destination - 1).unwrap();
-
-        destination.reverse();
-        destination.append(&mut removed_items);
-        destination.reverse();
-    }
-
-    let res = input
-        .into_iter()
-        .map(|value| value.iter().next().unwrap().to_string())
-        .collect::<Vec<String>>()
-        .join("");
-
-    res
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let mut input = vec![vec!["N", "Z"], vec!["D", "C", "M"], vec!["P"]];
-
-        let instructions = "move 1 from 2 to 1
-        move 3 from 1 to 3
-        move 2 from 2 to 1
-        move 1 from 1 to 2";
-
-        assert_eq!("CMZ", solution_1(&mut input, instructions));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let mut input = vec![
-            vec!["P", "Z", "M", "T", "R", "C", "N"],
-            vec!["Z", "B", "S", "T", "N", "D"],
-            vec!["G", "T", "C", "F", "R", "Q", "H", "M"],
-            vec!["Z", "R", "G"],
-            vec!["H", "R", "N", "Z"],
-            vec!["D", "L", "Z", "P", "W", "S", "H", "F"],
-            vec!["M", "G", "C", "R", "Z", "D", "W"],
-            vec!["Q", "Z", "W", "H", "L", "F", "J", "S"],
-            vec!["N", "W", "P", "Q", "S"]
-        ];
-
-        let instructions = include_str!("input/day5.txt");
-
-        assert_eq!("RTGWZTHLD", solution_1(&mut input, instructions));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let mut input = vec![vec!["N", "Z"], vec!["D", "C", "M"], vec!["P"]];
-
-        let instructions = "move 1 from 2 to 1
-        move 3 from 1 to 3
-        move 2 from 2 to 1
-        move 1 from 1 to 2";
-
-        assert_eq!("MCD", solution_2(&mut input, instructions));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let mut input = vec![
-            vec!["P", "Z", "M", "T", "R", "C", "N"],
-            vec!["Z", "B", "S", "T", "N", "D"],
-            vec!["G", "T", "C", "F", "R", "Q", "H", "M"],
-            vec!["Z", "R", "G"],
-            vec!["H", "R", "N", "Z"],
-            vec!["D", "L", "Z", "P", "W", "S", "H", "F"],
-            vec!["M", "G", "C", "R", "Z", "D", "W"],
-            vec!["Q", "Z", "W", "H", "L", "F", "J", "S"],
-            vec!["N", "W", "P", "Q", "S"]
-        ];
-
-        let instructions = include_str!("input/day5.txt");
-
-        assert_eq!("STHGRZZFR", solution_2(&mut input, instructions));
-    }
-}
+use std::fmt;
+
+use aoc_common::{parse::parse_number, Answer, ParamError, ParseError, Solution, SolveError};
+use itertools::Itertools;
+use regex::Regex;
+
+/// A `move <quantity> from <source> to <destination>` instruction, with 1-based stack numbers.
+pub type Move = (usize, usize, usize);
+
+/// The crate stacks drawn at the top of the puzzle input, top crate first, and the moves below
+/// them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procedure<'a> {
+    pub stacks: Vec<Vec<&'a str>>,
+    pub moves: Vec<Move>,
+}
+
+/// Parses the drawing of the stacks: rows of `[X]` crates 4 columns apart, standing on a line
+/// numbering the stacks from 1.
+fn parse_stacks<'a>(input: &'a str, drawing: &'a str) -> Result<Vec<Vec<&'a str>>, ParseError> {
+    let mut rows = drawing.lines().collect_vec();
+    let numbers = rows.pop().unwrap_or_default();
+
+    for (index, number) in numbers.split_whitespace().enumerate() {
+        if number != (index + 1).to_string() {
+            return Err(ParseError::at(
+                input,
+                number,
+                format!("expected stack {}", index + 1),
+            ));
+        }
+    }
+
+    let mut stacks = vec![vec![]; numbers.split_whitespace().count()];
+
+    for row in rows {
+        let mut rest = row;
+
+        for index in 0.. {
+            if rest.is_empty() {
+                break;
+            }
+
+            let end = rest
+                .char_indices()
+                .nth(3)
+                .map_or(rest.len(), |(end, _)| end);
+            let (cell, tail) = rest.split_at(end);
+
+            rest = match tail.strip_prefix(' ') {
+                Some(tail) => tail,
+                None if tail.is_empty() => tail,
+                None => return Err(ParseError::at(input, tail, "expected a space")),
+            };
+
+            if cell.trim().is_empty() {
+                continue;
+            }
+
+            let Some(name) = cell
+                .strip_prefix('[')
+                .and_then(|cell| cell.strip_suffix(']'))
+                .filter(|name| name.chars().count() == 1)
+            else {
+                return Err(ParseError::at(
+                    input,
+                    cell,
+                    "expected `[<crate>]` or spaces",
+                ));
+            };
+
+            match stacks.get_mut(index) {
+                Some(stack) => stack.push(name),
+                None => {
+                    return Err(ParseError::at(
+                        input,
+                        cell,
+                        format!("there is no stack {}", index + 1),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn parse_moves(input: &str, moves: &str) -> Result<Vec<Move>, ParseError> {
+    let re = Regex::new("^move ([0-9]+) from ([1-9][0-9]*) to ([1-9][0-9]*)$").unwrap();
+
+    moves
+        .trim()
+        .lines()
+        .map(|instruction| {
+            let instruction = instruction.trim();
+            let matches = re.captures(instruction).ok_or_else(|| {
+                ParseError::at(
+                    input,
+                    instruction,
+                    "expected `move <quantity> from <stack> to <stack>`",
+                )
+            })?;
+            let number = |group| parse_number(input, &matches[group]);
+
+            Ok((number(1)?, number(2)?, number(3)?))
+        })
+        .collect()
+}
+
+/// Parses the drawing of the stacks and the moves after it. The drawing can be left out, for the
+/// stacks to be given as [`Params`] instead.
+pub fn parse(input: &str) -> Result<Procedure<'_>, ParseError> {
+    let input = input.trim_start_matches(['\r', '\n']);
+
+    let (drawing, moves) = match input.starts_with("move") {
+        true => ("", input),
+        false => input
+            .split_once("\n\n")
+            .or_else(|| input.split_once("\r\n\r\n"))
+            .unwrap_or((input, "")),
+    };
+
+    Ok(Procedure {
+        stacks: parse_stacks(input, drawing)?,
+        moves: parse_moves(input, moves)?,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CraneError {
+    /// A move names a stack that does not exist. Moves are numbered from 1.
+    NoStack { step: usize, stack: usize },
+    /// A move takes more crates than the stack holds.
+    NotEnoughCrates {
+        step: usize,
+        stack: usize,
+        wanted: usize,
+        held: usize,
+    },
+    /// A stack ends up empty, so it has no crate on top.
+    Empty { stack: usize },
+}
+
+impl fmt::Display for CraneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CraneError::NoStack { step, stack } => {
+                write!(f, "move {step} uses stack {stack}, which does not exist")
+            }
+            CraneError::NotEnoughCrates {
+                step,
+                stack,
+                wanted,
+                held,
+            } => write!(
+                f,
+                "move {step} takes {wanted} crates from stack {stack}, which holds {held}"
+            ),
+            CraneError::Empty { stack } => write!(f, "stack {stack} ends up empty"),
+        }
+    }
+}
+
+impl std::error::Error for CraneError {}
+
+/// Carries out the moves, lifting crates one at a time or, when `together` is set, all of a move's
+/// crates at once, and reads the crate on top of each stack.
+fn rearrange(stacks: &[Vec<&str>], moves: &[Move], together: bool) -> Result<String, CraneError> {
+    let mut stacks = stacks.to_vec();
+
+    for (index, &(quantity, source, destination)) in moves.iter().enumerate() {
+        let step = index + 1;
+
+        for stack in [source, destination] {
+            if stack == 0 || stack > stacks.len() {
+                return Err(CraneError::NoStack { step, stack });
+            }
+        }
+
+        let held = stacks[source - 1].len();
+
+        if quantity > held {
+            return Err(CraneError::NotEnoughCrates {
+                step,
+                stack: source,
+                wanted: quantity,
+                held,
+            });
+        }
+
+        let mut lifted = stacks[source - 1].drain(..quantity).collect_vec();
+
+        if !together {
+            lifted.reverse();
+        }
+
+        stacks[destination - 1].splice(0..0, lifted);
+    }
+
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(index, stack)| {
+            stack
+                .first()
+                .copied()
+                .ok_or(CraneError::Empty { stack: index + 1 })
+        })
+        .collect()
+}
+
+pub fn solution_1(stacks: &[Vec<&str>], instructions: &[Move]) -> Result<String, CraneError> {
+    rearrange(stacks, instructions, false)
+}
+
+pub fn solution_2(stacks: &[Vec<&str>], instructions: &[Move]) -> Result<String, CraneError> {
+    rearrange(stacks, instructions, true)
+}
+
+pub struct Day5;
+
+/// The crate stacks to start from, top crate first, instead of those drawn in the input. Set by
+/// name as comma separated stacks, e.g. `stacks=NZ,DCM,P`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub stacks: Vec<Vec<String>>,
+}
+
+impl Params {
+    /// The stacks to start from, falling back to the `drawn` ones when none are set.
+    fn stacks<'a>(&'a self, drawn: &[Vec<&'a str>]) -> Vec<Vec<&'a str>> {
+        match self.stacks.is_empty() {
+            true => drawn.to_vec(),
+            false => self
+                .stacks
+                .iter()
+                .map(|stack| stack.iter().map(String::as_str).collect())
+                .collect(),
+        }
+    }
+}
+
+impl aoc_common::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+        match name {
+            "stacks" if value.trim().is_empty() => self.stacks = vec![],
+            "stacks" => {
+                self.stacks = value
+                    .split(',')
+                    .map(|stack| stack.trim().chars().map(String::from).collect())
+                    .collect()
+            }
+            _ => return Err(ParamError::Unknown(name.to_string())),
+        }
+
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![(
+            "stacks",
+            self.stacks.iter().map(|stack| stack.concat()).join(","),
+        )]
+    }
+}
+
+impl Solution for Day5 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Params = Params;
+    type Input<'a> = Procedure<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, params: &Params) -> Result<Answer, SolveError> {
+        solution_1(&params.stacks(&input.stacks), &input.moves)
+            .map(Answer::from)
+            .map_err(SolveError::solve)
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Params) -> Result<Answer, SolveError> {
+        solution_2(&params.stacks(&input.stacks), &input.moves)
+            .map(Answer::from)
+            .map_err(SolveError::solve)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{example::parse_examples, input_or_skip};
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(5));
+        let procedure = parse(&input).unwrap();
+
+        assert_eq!(
+            Ok("RTGWZTHLD".to_string()),
+            solution_1(&procedure.stacks, &procedure.moves)
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_instructions() {
+        let instructions = "move 1 from 2 to 1
+        move 3 from 0 to 3";
+
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                9,
+                "move 3 from 0 to 3",
+                "expected `move <quantity> from <stack> to <stack>`"
+            )),
+            parse(instructions)
+        );
+    }
+
+    fn example() -> String {
+        parse_examples(include_str!("examples/day5.toml")).unwrap()[0]
+            .input
+            .clone()
+    }
+
+    #[test]
+    fn parse_the_drawing() {
+        let input = example();
+        let procedure = parse(&input).unwrap();
+
+        assert_eq!(
+            vec![vec!["N", "Z"], vec!["D", "C", "M"], vec!["P"]],
+            procedure.stacks
+        );
+        assert_eq!(4, procedure.moves.len());
+
+        assert_eq!(
+            Err(ParseError::new(2, 6, "3", "expected stack 2")),
+            parse("[A]\n 1   3\n\nmove 1 from 1 to 1").map(|procedure| procedure.stacks)
+        );
+        assert_eq!(
+            Err(ParseError::new(
+                1,
+                5,
+                "(B)",
+                "expected `[<crate>]` or spaces"
+            )),
+            parse("[A] (B)\n 1   2\n\nmove 1 from 1 to 2").map(|procedure| procedure.stacks)
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 5, "[B]", "there is no stack 2")),
+            parse("[A] [B]\n 1\n\nmove 1 from 1 to 1").map(|procedure| procedure.stacks)
+        );
+    }
+
+    #[test]
+    fn check_each_move() {
+        let input = example();
+        let stacks = parse(&input).unwrap().stacks;
+
+        assert_eq!(
+            Err(CraneError::NoStack { step: 2, stack: 4 }),
+            solution_1(&stacks, &[(1, 1, 2), (1, 4, 1)])
+        );
+        assert_eq!(
+            Err(CraneError::NotEnoughCrates {
+                step: 1,
+                stack: 2,
+                wanted: 4,
+                held: 3
+            }),
+            solution_2(&stacks, &[(4, 2, 1)])
+        );
+        assert_eq!(
+            Err(CraneError::Empty { stack: 3 }),
+            solution_1(&stacks, &[(1, 3, 1)])
+        );
+        assert_eq!(
+            Err(SolveError::Solve(
+                "move 1 takes 4 crates from stack 2, which holds 3".to_string()
+            )),
+            Day5::part2(
+                &Procedure {
+                    stacks,
+                    moves: vec![(4, 2, 1)]
+                },
+                &Params::default()
+            )
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(5));
+        let procedure = parse(&input).unwrap();
+
+        assert_eq!(
+            Ok("STHGRZZFR".to_string()),
+            solution_2(&procedure.stacks, &procedure.moves)
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::BTreeSet;
 
+use aoc_common::{Answer, ParseError, Solution, SolveError};
+
 fn get_marker_index(input: &str, window_size: usize) -> Option<usize> {
     input
         .as_bytes()
@@ -13,42 +15,58 @@ fn get_marker_index(input: &str, window_size: usize) -> Option<usize> {
         .map(|(index, _)| index + window_size)
 }
 
-fn solution_1(input: &str) -> Option<usize> {
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    Ok(input.trim())
+}
+
+pub fn solution_1(input: &str) -> Option<usize> {
     get_marker_index(input, 4)
 }
 
-fn solution_2(input: &str) -> Option<usize> {
+pub fn solution_2(input: &str) -> Option<usize> {
     get_marker_index(input, 14)
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Params = ();
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::input_or_skip;
 
-    #[test]
-    fn solution_1_example() {
-        assert_eq!(solution_1("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 5);
-        assert_eq!(solution_1("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 6);
-        assert_eq!(solution_1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), 10);
-        assert_eq!(solution_1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), 11);
-    }
+    use super::*;
 
     #[test]
     fn solution_1_input() {
-        assert_eq!(solution_1(include_str!("input/day6.txt")).unwrap(), 1109);
-    }
+        let input = input_or_skip!(crate::inputs().load(6));
 
-    #[test]
-    fn solution_2_example() {
-        assert_eq!(solution_2("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), 19);
-        assert_eq!(solution_2("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 23);
-        assert_eq!(solution_2("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 23);
-        assert_eq!(solution_2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), 29);
-        assert_eq!(solution_2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), 26);
+        assert_eq!(solution_1(parse(&input).unwrap()).unwrap(), 1109);
     }
 
     #[test]
     fn solution_2_input() {
-        assert_eq!(solution_2(include_str!("input/day6.txt")).unwrap(), 3965);
+        let input = input_or_skip!(crate::inputs().load(6));
+
+        assert_eq!(solution_2(parse(&input).unwrap()).unwrap(), 3965);
     }
 }
@@ -1,273 +1,313 @@
-use std::{cell::RefCell, rc::Rc};
-
-#[derive(PartialEq)]
-enum FileType {
-    File,
-    Directory,
-}
-
-struct File<'a> {
-    name: &'a str,
-    size: Option<usize>,
-    file_type: FileType,
-
-    children: Option<Vec<Rc<RefCell<File<'a>>>>>,
-    parent: Option<Rc<RefCell<File<'a>>>>,
-}
-
-impl<'a> File<'a> {
-    fn add_child_item(&mut self, item: Rc<RefCell<File<'a>>>) {
-        self.children.as_mut().unwrap().push(Rc::clone(&item));
-    }
-
-    fn all_children(&self) -> Option<&Vec<Rc<RefCell<File<'a>>>>> {
-        self.children.as_ref()
-    }
-
-    fn get_child_item(&self, name: &str) -> Option<Rc<RefCell<File<'a>>>> {
-        self.all_children()?
-            .iter()
-            .find(|&child| child.borrow().name == name)
-            .map(|res| Rc::clone(res))
-    }
-
-    fn get_directories(&self) -> Option<Vec<Rc<RefCell<Self>>>> {
-        Some(
-            self.all_children()?
-                .iter()
-                .filter(|&child| child.borrow().file_type == FileType::Directory)
-                .map(|res| Rc::clone(&res))
-                .collect(),
-        )
-    }
-
-    fn get_parent(&self) -> Option<Rc<RefCell<Self>>> {
-        match &self.parent {
-            Some(parent) => Some(Rc::clone(&parent)),
-            None => unreachable!(),
-        }
-    }
-
-    fn size(&self) -> usize {
-        match self.file_type {
-            FileType::File => self.size.unwrap(),
-            FileType::Directory => self
-                .all_children()
-                .unwrap()
-                .iter()
-                .fold(0, |curr, acc| curr + acc.borrow().size()),
-        }
-    }
-
-    fn total_size(&self) -> Vec<usize> {
-        let mut sizes = vec![self.size()];
-
-        match self.get_directories() {
-            Some(dirs) => {
-                let mut dir_sizes = dirs
-                    .iter()
-                    .flat_map(|x| x.borrow().total_size())
-                    .collect::<Vec<_>>();
-                sizes.append(&mut dir_sizes);
-            }
-            None => {}
-        }
-
-        sizes
-    }
-
-    fn parse(input: &str) -> Rc<RefCell<File>> {
-        let parsed_commands = ParserResult::parse_input(input);
-        let root = Rc::new(RefCell::new(File::default()));
-        let mut cwd = Rc::clone(&root);
-
-        for instruction in parsed_commands {
-            match instruction {
-                ParserResult::ChangeDirUp => {
-                    let target_dir = cwd.borrow().get_parent().unwrap();
-
-                    cwd = target_dir;
-                }
-                ParserResult::ChangeDir(dir) => {
-                    let target_dir = cwd.borrow().get_child_item(dir).unwrap();
-
-                    cwd = target_dir;
-                }
-                ParserResult::Directory(dir) => {
-                    let directory = File {
-                        name: dir,
-                        file_type: FileType::Directory,
-                        size: None,
-                        parent: Some(Rc::clone(&cwd)),
-                        children: Some(vec![]),
-                    };
-
-                    cwd.borrow_mut()
-                        .add_child_item(Rc::new(RefCell::new(directory)));
-                }
-                ParserResult::File(file, size) => {
-                    let file = File {
-                        name: file,
-                        file_type: FileType::File,
-                        size: Some(size),
-                        parent: Some(Rc::clone(&cwd)),
-                        children: None,
-                    };
-
-                    cwd.borrow_mut().add_child_item(Rc::new(RefCell::new(file)));
-                }
-            }
-        }
-
-        root
-    }
-}
-
-impl<'a> Default for File<'a> {
-    fn default() -> Self {
-        Self {
-            name: "/",
-            size: None,
-            file_type: FileType::Directory,
-            children: Some(vec![]),
-            parent: None,
-        }
-    }
-}
-
-#[derive(Debug)]
-enum ParserResult<'a> {
-    ChangeDirUp,
-    ChangeDir(&'a str),
-    Directory(&'a str),
-    File(&'a str, usize),
-}
-
-impl ParserResult<'static> {
-    fn parse_input(input: &str) -> Vec<ParserResult> {
-        input
-            .trim()
-            .lines()
-            .skip(2)
-            .filter_map(|line| {
-                let split = line.trim().split_whitespace().collect::<Vec<_>>();
-
-                match split[..] {
-                    ["$", "cd", arg] => match arg {
-                        "/" => None,
-                        ".." => Some(ParserResult::ChangeDirUp),
-                        dir => Some(ParserResult::ChangeDir(dir)),
-                    },
-                    ["$", "ls"] => None,
-                    ["dir", second] => Some(ParserResult::Directory(second)),
-                    [size, name] => Some(ParserResult::File(name, size.parse::<usize>().unwrap())),
-                    _ => None,
-                }
-            })
-            .collect::<Vec<_>>()
-    }
-}
-
-fn solution_1(input: &str) -> usize {
-    let file_system = File::parse(input);
-    let file_system = file_system.borrow();
-
-    let total_sizes = file_system.total_size();
-
-    total_sizes.into_iter().filter(|&size| size <= 100000).sum()
-}
-
-fn solution_2(input: &str) -> Option<usize> {
-    const MAX_SPACE: usize = 70_000_000;
-    const REQUIRED_SPACE: usize = 30_000_000;
-
-    let file_system = File::parse(input);
-    let file_system = file_system.borrow();
-    let total_size = file_system.size();
-
-    let remaining_space = MAX_SPACE - total_size;
-    let space_to_clear = REQUIRED_SPACE - remaining_space;
-
-    file_system
-        .total_size()
-        .into_iter()
-        .filter(|&size| size >= space_to_clear)
-        .min()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "$ cd /
-        $ ls
-        dir a
-        14848514 b.txt
-        8504156 c.dat
-        dir d
-        $ cd a
-        $ ls
-        dir e
-        29116 f
-        2557 g
-        62596 h.lst
-        $ cd e
-        $ ls
-        584 i
-        $ cd ..
-        $ cd ..
-        $ cd d
-        $ ls
-        4060174 j
-        8033020 d.log
-        5626152 d.ext
-        7214296 k";
-
-        assert_eq!(95437, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day7.txt");
-
-        assert_eq!(1543140, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "$ cd /
-        $ ls
-        dir a
-        14848514 b.txt
-        8504156 c.dat
-        dir d
-        $ cd a
-        $ ls
-        dir e
-        29116 f
-        2557 g
-        62596 h.lst
-        $ cd e
-        $ ls
-        584 i
-        $ cd ..
-        $ cd ..
-        $ cd d
-        $ ls
-        4060174 j
-        8033020 d.log
-        5626152 d.ext
-        7214296 k";
-
-        assert_eq!(24933642, solution_2(input).unwrap());
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day7.txt");
-
-        assert_eq!(1117448, solution_2(input).unwrap());
-    }
-}
+use std::{cell::RefCell, rc::Rc};
+
+use aoc_common::{parse::parse_number, Answer, ParseError, Solution, SolveError};
+
+#[derive(PartialEq)]
+enum FileType {
+    File,
+    Directory,
+}
+
+pub struct File<'a> {
+    name: &'a str,
+    size: Option<usize>,
+    file_type: FileType,
+
+    children: Option<Vec<Rc<RefCell<File<'a>>>>>,
+    parent: Option<Rc<RefCell<File<'a>>>>,
+}
+
+impl<'a> File<'a> {
+    fn add_child_item(&mut self, item: Rc<RefCell<File<'a>>>) {
+        self.children.as_mut().unwrap().push(Rc::clone(&item));
+    }
+
+    fn all_children(&self) -> Option<&Vec<Rc<RefCell<File<'a>>>>> {
+        self.children.as_ref()
+    }
+
+    fn get_child_item(&self, name: &str) -> Option<Rc<RefCell<File<'a>>>> {
+        self.all_children()?
+            .iter()
+            .find(|&child| child.borrow().name == name)
+            .map(Rc::clone)
+    }
+
+    fn get_directories(&self) -> Option<Vec<Rc<RefCell<Self>>>> {
+        Some(
+            self.all_children()?
+                .iter()
+                .filter(|&child| child.borrow().file_type == FileType::Directory)
+                .map(Rc::clone)
+                .collect(),
+        )
+    }
+
+    fn get_parent(&self) -> Option<Rc<RefCell<Self>>> {
+        self.parent.as_ref().map(Rc::clone)
+    }
+
+    fn size(&self) -> usize {
+        match self.file_type {
+            FileType::File => self.size.unwrap(),
+            FileType::Directory => self
+                .all_children()
+                .unwrap()
+                .iter()
+                .fold(0, |curr, acc| curr + acc.borrow().size()),
+        }
+    }
+
+    fn total_size(&self) -> Vec<usize> {
+        let mut sizes = vec![self.size()];
+
+        if let Some(dirs) = self.get_directories() {
+            let mut dir_sizes = dirs
+                .iter()
+                .flat_map(|x| x.borrow().total_size())
+                .collect::<Vec<_>>();
+            sizes.append(&mut dir_sizes);
+        }
+
+        sizes
+    }
+
+    fn parse(input: &str) -> Result<Rc<RefCell<File<'_>>>, ParseError> {
+        let parsed_commands = ParserResult::parse_input(input)?;
+        let root = Rc::new(RefCell::new(File::default()));
+        let mut cwd = Rc::clone(&root);
+
+        for (line, instruction) in parsed_commands {
+            match instruction {
+                ParserResult::ChangeDirUp => {
+                    let target_dir = cwd.borrow().get_parent().ok_or_else(|| {
+                        ParseError::at(input, line, "cannot leave the root directory")
+                    })?;
+
+                    cwd = target_dir;
+                }
+                ParserResult::ChangeDirRoot => cwd = Rc::clone(&root),
+                ParserResult::ChangeDir(dir) => {
+                    let target_dir = cwd.borrow().get_child_item(dir).ok_or_else(|| {
+                        ParseError::at(input, line, "no such directory has been listed")
+                    })?;
+
+                    if target_dir.borrow().file_type != FileType::Directory {
+                        return Err(ParseError::at(
+                            input,
+                            line,
+                            format!("`{dir}` is not a directory"),
+                        ));
+                    }
+
+                    cwd = target_dir;
+                }
+                ParserResult::Directory(dir) => {
+                    let directory = File {
+                        name: dir,
+                        file_type: FileType::Directory,
+                        size: None,
+                        parent: Some(Rc::clone(&cwd)),
+                        children: Some(vec![]),
+                    };
+
+                    cwd.borrow_mut()
+                        .add_child_item(Rc::new(RefCell::new(directory)));
+                }
+                ParserResult::File(file, size) => {
+                    let file = File {
+                        name: file,
+                        file_type: FileType::File,
+                        size: Some(size),
+                        parent: Some(Rc::clone(&cwd)),
+                        children: None,
+                    };
+
+                    cwd.borrow_mut().add_child_item(Rc::new(RefCell::new(file)));
+                }
+            }
+        }
+
+        Ok(root)
+    }
+}
+
+impl<'a> Default for File<'a> {
+    fn default() -> Self {
+        Self {
+            name: "/",
+            size: None,
+            file_type: FileType::Directory,
+            children: Some(vec![]),
+            parent: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum ParserResult<'a> {
+    ChangeDirRoot,
+    ChangeDirUp,
+    ChangeDir(&'a str),
+    Directory(&'a str),
+    File(&'a str, usize),
+}
+
+impl ParserResult<'static> {
+    /// Parses every line of a session that starts in the root directory, along with the line it
+    /// came from.
+    fn parse_input(input: &str) -> Result<Vec<(&str, ParserResult<'_>)>, ParseError> {
+        let mut lines = input.trim().lines().peekable();
+
+        match lines.peek() {
+            Some(line) if line.trim() == "$ cd /" => {}
+            Some(line) => return Err(ParseError::at(input, line, "expected `$ cd /`")),
+            None => return Err(ParseError::end_of(input, "expected `$ cd /`")),
+        }
+
+        lines
+            .filter_map(|line| {
+                let line = line.trim();
+                let split = line.split_whitespace().collect::<Vec<_>>();
+
+                let result = match split[..] {
+                    ["$", "cd", arg] => match arg {
+                        "/" => Ok(ParserResult::ChangeDirRoot),
+                        ".." => Ok(ParserResult::ChangeDirUp),
+                        dir => Ok(ParserResult::ChangeDir(dir)),
+                    },
+                    ["$", "ls"] => return None,
+                    ["dir", second] => Ok(ParserResult::Directory(second)),
+                    [size, name] => {
+                        parse_number(input, size).map(|size| ParserResult::File(name, size))
+                    }
+                    _ => Err(ParseError::at(
+                        input,
+                        line,
+                        "expected a command or a listed file",
+                    )),
+                };
+
+                Some(result.map(|result| (line, result)))
+            })
+            .collect()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Rc<RefCell<File<'_>>>, ParseError> {
+    File::parse(input)
+}
+
+pub fn solution_1(file_system: &Rc<RefCell<File>>) -> usize {
+    let file_system = file_system.borrow();
+
+    let total_sizes = file_system.total_size();
+
+    total_sizes.into_iter().filter(|&size| size <= 100000).sum()
+}
+
+/// The size of the smallest directory to delete to free up enough space, `Some(0)` when there is
+/// already enough, or `None` when the files don't fit on the disk.
+pub fn solution_2(file_system: &Rc<RefCell<File>>) -> Option<usize> {
+    const MAX_SPACE: usize = 70_000_000;
+    const REQUIRED_SPACE: usize = 30_000_000;
+
+    let file_system = file_system.borrow();
+    let total_size = file_system.size();
+
+    let remaining_space = MAX_SPACE.checked_sub(total_size)?;
+    let space_to_clear = REQUIRED_SPACE.saturating_sub(remaining_space);
+
+    if space_to_clear == 0 {
+        return Some(0);
+    }
+
+    file_system
+        .total_size()
+        .into_iter()
+        .filter(|&size| size >= space_to_clear)
+        .min()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    type Params = ();
+    type Input<'a> = Rc<RefCell<File<'a>>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(7));
+
+        assert_eq!(1543140, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_directories() {
+        let err = parse("$ cd /\n$ ls\ndir a\n$ cd b").err().unwrap();
+
+        assert_eq!(
+            ParseError::new(4, 1, "$ cd b", "no such directory has been listed"),
+            err
+        );
+        assert!(parse("$ cd /\n$ ls\n$ cd ..").is_err());
+        assert!(parse("$ cd /\n$ ls\nabc 12").is_err());
+    }
+
+    #[test]
+    fn parse_checks_the_session() {
+        assert_eq!(
+            Err(ParseError::new(4, 1, "$ cd a", "`a` is not a directory")),
+            parse("$ cd /\n$ ls\n12 a\n$ cd a\n$ ls\n1 b").map(|_| ())
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 1, "$ ls", "expected `$ cd /`")),
+            parse("$ ls\n12 a").map(|_| ())
+        );
+        assert!(parse("").is_err());
+
+        // Going back to the root lists `c` next to `a`, not inside it.
+        let root = parse("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n1 b\n$ cd /\n$ ls\n2 c").unwrap();
+
+        assert_eq!(vec![3, 1], root.borrow().total_size());
+    }
+
+    #[test]
+    fn solution_2_checks_free_space() {
+        assert_eq!(Some(0), solution_2(&parse("$ cd /\n$ ls\n12 a").unwrap()));
+        assert_eq!(
+            None,
+            solution_2(&parse("$ cd /\n$ ls\n70000001 a").unwrap())
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(7));
+
+        assert_eq!(1117448, solution_2(&parse(&input).unwrap()).unwrap());
+    }
+}
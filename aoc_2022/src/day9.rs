@@ -1,129 +1,126 @@
-use std::collections::HashSet;
-
-struct Direction<'a>(&'a str, i32);
-
-#[derive(Clone, Copy, PartialEq, PartialOrd, Hash, Eq)]
-struct Vector(i32, i32);
-
-fn get_positions(input: &str, followers: u8) -> Vec<Vector> {
-    let moves: Vec<Direction> = input
-        .trim()
-        .lines()
-        .map(|line| {
-            let mut split = line.split_whitespace();
-            let dir = split.next().unwrap();
-            let moves = split.next().unwrap().parse::<i32>().unwrap();
-
-            Direction(dir, moves)
-        })
-        .collect();
-
-    let mut snake = vec![Vector(0, 0); followers.into()];
-
-    let mut visited_positions = vec![];
-    visited_positions.push(Vector(0, 0));
-
-    for _move in moves {
-        for _ in 1..=_move.1 {
-            move_snake(&mut snake, _move.0);
-
-            visited_positions.push(snake.last().unwrap().clone());
-        }
-    }
-
-    visited_positions
-}
-
-fn move_snake(snake: &mut Vec<Vector>, dir: &str) {
-    let turn_pos = match dir.to_uppercase().as_str() {
-        "U" => Vector(0, 1),
-        "D" => Vector(0, -1),
-        "L" => Vector(-1, 0),
-        "R" => Vector(1, 0),
-        _ => unreachable!(),
-    };
-
-    let head = snake.get_mut(0).unwrap();
-
-    head.0 += turn_pos.0;
-    head.1 += turn_pos.1;
-
-    for index in 1..snake.iter().len() {
-        let x = snake[index - 1].0 - snake[index].0;
-        let y = snake[index - 1].1 - snake[index].1;
-
-        if x.abs() > 1 || y.abs() > 1 {
-            let tail = &mut snake[index];
-
-            tail.0 += x.signum();
-            tail.1 += y.signum();
-        }
-    }
-}
-
-fn solution_1(input: &str) -> i32 {
-    get_positions(input, 2)
-        .into_iter()
-        .collect::<HashSet<Vector>>()
-        .iter()
-        .count()
-        .try_into()
-        .unwrap()
-}
-
-fn solution_2(input: &str) -> i32 {
-    get_positions(input, 10)
-        .into_iter()
-        .collect::<HashSet<Vector>>()
-        .iter()
-        .count()
-        .try_into()
-        .unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn solution_1_example() {
-        let input = "R 4
-        U 4
-        L 3
-        D 1
-        R 4
-        D 1
-        L 5
-        R 2";
-
-        assert_eq!(13, solution_1(input));
-    }
-
-    #[test]
-    fn solution_1_input() {
-        let input = include_str!("input/day9.txt");
-
-        assert_eq!(5878, solution_1(input));
-    }
-
-    #[test]
-    fn solution_2_example() {
-        let input = "R 4
-        U 4
-        L 3
-        D 1
-        R 4
-        D 1
-        L 5
-        R 2";
-
-        assert_eq!(1, solution_2(input));
-    }
-
-    #[test]
-    fn solution_2_input() {
-        let input = include_str!("input/day9.txt");
-
-        assert_eq!(2405, solution_2(input));
-    }
-}
+use std::collections::HashSet;
+
+use aoc_common::{parse::parse_number, Answer, Dir4, ParseError, Point2, Solution, SolveError};
+
+type Vector = Point2<i32>;
+
+pub struct Direction(Dir4, i32);
+
+pub fn parse(input: &str) -> Result<Vec<Direction>, ParseError> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let (dir, moves) = line.split_once(' ').ok_or_else(|| {
+                ParseError::at(input, line, "expected a direction and a distance")
+            })?;
+            let dir = dir
+                .parse::<Dir4>()
+                .map_err(|_| ParseError::at(input, dir, "expected one of `UDLR`"))?;
+
+            Ok(Direction(dir, parse_number(input, moves)?))
+        })
+        .collect()
+}
+
+fn get_positions(moves: &[Direction], followers: u8) -> Vec<Vector> {
+    let mut snake = vec![Vector::ORIGIN; followers.into()];
+
+    let mut visited_positions = vec![];
+    visited_positions.push(Vector::ORIGIN);
+
+    for _move in moves {
+        for _ in 1..=_move.1 {
+            move_snake(&mut snake, _move.0);
+
+            visited_positions.push(*snake.last().unwrap());
+        }
+    }
+
+    visited_positions
+}
+
+fn move_snake(snake: &mut [Vector], dir: Dir4) {
+    snake[0] += dir.offset();
+
+    for index in 1..snake.iter().len() {
+        let head = snake[index - 1];
+
+        if head.chebyshev(snake[index]) > 1 {
+            snake[index] = snake[index].step_towards(head);
+        }
+    }
+}
+
+pub fn solution_1(moves: &[Direction]) -> i32 {
+    get_positions(moves, 2)
+        .into_iter()
+        .collect::<HashSet<Vector>>().len()
+        .try_into()
+        .unwrap()
+}
+
+pub fn solution_2(moves: &[Direction]) -> i32 {
+    get_positions(moves, 10)
+        .into_iter()
+        .collect::<HashSet<Vector>>().len()
+        .try_into()
+        .unwrap()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u16 = 2022;
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    type Params = ();
+    type Input<'a> = Vec<Direction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
+
+    #[test]
+    fn solution_1_input() {
+        let input = input_or_skip!(crate::inputs().load(9));
+
+        assert_eq!(5878, solution_1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn parse_reports_bad_moves() {
+        assert_eq!(
+            Err(ParseError::new(2, 1, "X", "expected one of `UDLR`")),
+            parse("R 4\nX 4").map(|moves| moves.len())
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 3, "four", "expected a number")),
+            parse("R four").map(|moves| moves.len())
+        );
+    }
+
+    #[test]
+    fn solution_2_input() {
+        let input = input_or_skip!(crate::inputs().load(9));
+
+        assert_eq!(2405, solution_2(&parse(&input).unwrap()));
+    }
+}
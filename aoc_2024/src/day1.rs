@@ -1,16 +1,21 @@
 use std::collections::HashMap;
 
-pub fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
-    input
+use aoc_common::{parse::parse_number, Answer, ParseError, Solution, SolveError};
+
+pub fn parse(input: &str) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
+    let pairs = input
         .lines()
         .map(|e| {
-            let mut split = e.split_whitespace();
-            (
-                split.next().map(|x| x.parse::<u32>().unwrap()).unwrap(),
-                split.next().map(|x| x.parse::<u32>().unwrap()).unwrap(),
-            )
+            let split = e.split_whitespace().collect::<Vec<_>>();
+
+            match split[..] {
+                [left, right] => Ok((parse_number(input, left)?, parse_number(input, right)?)),
+                _ => Err(ParseError::at(input, e, "expected two location ids")),
+            }
         })
-        .unzip()
+        .collect::<Result<Vec<(u32, u32)>, _>>()?;
+
+    Ok(pairs.into_iter().unzip())
 }
 
 pub fn sort_input(mut input: (Vec<u32>, Vec<u32>)) -> (Vec<u32>, Vec<u32>) {
@@ -20,25 +25,23 @@ pub fn sort_input(mut input: (Vec<u32>, Vec<u32>)) -> (Vec<u32>, Vec<u32>) {
     (input.0, input.1)
 }
 
-fn solution_1(input: &str) -> u32 {
-    let split_input = sort_input(parse_input(input));
+pub fn solution_1(input: &(Vec<u32>, Vec<u32>)) -> u32 {
+    let split_input = sort_input(input.clone());
     let left = split_input.0;
     let right = split_input.1;
 
     left.into_iter()
-        .zip(right.into_iter())
+        .zip(right)
         .map(|(l, r)| l.abs_diff(r))
         .sum()
 }
 
-fn solution_2(input: &str) -> u32 {
-    let parsed_input = parse_input(input);
-    let left = parsed_input.0;
-    let right = parsed_input.1;
+pub fn solution_2(input: &(Vec<u32>, Vec<u32>)) -> u32 {
+    let (left, right) = input;
 
     let mut hashmap = HashMap::new();
 
-    left.into_iter().fold(0, |val, item| {
+    left.iter().fold(0, |val, &item| {
         let value = hashmap
             .entry(item)
             .or_insert(right.iter().filter(|&&y| item == y).count() as u32);
@@ -47,45 +50,58 @@ fn solution_2(input: &str) -> u32 {
     })
 }
 
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+
+    type Params = ();
+    type Input<'a> = (Vec<u32>, Vec<u32>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use aoc_common::input_or_skip;
 
-    #[test]
-    pub fn solution_1_example() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-        assert_eq!(11, solution_1(input));
-    }
+    use super::*;
 
     #[test]
     pub fn solution_1_real() {
-        let input = include_str!("input_files/day1.txt");
+        let input = input_or_skip!(crate::inputs().load(1));
 
-        assert_eq!(1320851, solution_1(input));
+        assert_eq!(1320851, solution_1(&parse(&input).unwrap()));
     }
 
     #[test]
-    pub fn solution_2_example() {
-        let input = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-        assert_eq!(31, solution_2(input));
+    pub fn parse_requires_two_ids_per_line() {
+        assert_eq!(
+            Err(ParseError::new(2, 1, "4", "expected two location ids")),
+            parse("3   4\n4\n2   5")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 5, "x", "expected a number")),
+            parse("3   x")
+        );
     }
 
     #[test]
     pub fn solution_2_real() {
-        let input = include_str!("input_files/day1.txt");
+        let input = input_or_skip!(crate::inputs().load(1));
 
-        assert_eq!(26859182, solution_2(input));
+        assert_eq!(26859182, solution_2(&parse(&input).unwrap()));
     }
 }
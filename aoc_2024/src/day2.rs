@@ -1,24 +1,22 @@
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
+use aoc_common::{parse::parse_number, Answer, ParseError, Solution, SolveError};
+
+pub fn parse(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
     input
         .trim()
         .lines()
         .map(|line| {
             line.split_whitespace()
-                .filter_map(|num| num.parse::<u32>().ok())
+                .map(|num| parse_number(input, num))
                 .collect()
         })
         .collect()
 }
 
-fn solution_1(input: &str) -> usize {
-    let input = parse_input(input);
-
-    input.into_iter().filter(|row| is_safe(row)).count()
+pub fn solution_1(rows: &[Vec<u32>]) -> usize {
+    rows.iter().filter(|row| is_safe(row)).count()
 }
 
-fn solution_2(input: &str) -> usize {
-    let rows = parse_input(input).into_iter().collect::<Vec<Vec<u32>>>();
-
+pub fn solution_2(rows: &[Vec<u32>]) -> usize {
     let safe_rows = rows
         .iter()
         .filter(|&row| is_safe(row))
@@ -33,8 +31,13 @@ fn solution_2(input: &str) -> usize {
     unsafe_rows.len() + safe_rows.len()
 }
 
+/// Whether the levels all rise or all fall by 1 to 3, which a report with fewer than two levels
+/// trivially does.
 fn is_safe(row: &[u32]) -> bool {
-    let is_increasing = row[1] > row[0];
+    let [first, second, ..] = row else {
+        return true;
+    };
+    let is_increasing = second > first;
     row.windows(2).all(|row| {
         ((is_increasing && row[1] > row[0]) || (!is_increasing && row[1] < row[0]))
             && row[1].abs_diff(row[0]) <= 3
@@ -55,45 +58,62 @@ fn sanitize_row(row: &[u32]) -> Option<Vec<u32>> {
     None
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+
+    type Params = ();
+    type Input<'a> = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use aoc_common::input_or_skip;
+
     use super::*;
 
     #[test]
-    fn solution_1_example() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-        assert_eq!(2, solution_1(input));
+    fn solution_1_real() {
+        let input = input_or_skip!(crate::inputs().load(2));
+
+        assert_eq!(356, solution_1(&parse(&input).unwrap()));
     }
 
     #[test]
-    fn solution_1_real() {
-        let input = include_str!("input_files/day2.txt");
-
-        assert_eq!(356, solution_1(input));
+    fn parse_rejects_non_numbers() {
+        assert_eq!(
+            Err(ParseError::new(2, 3, "-2", "expected a number")),
+            parse("7 6 4\n1 -2 7")
+        );
     }
 
     #[test]
-    fn solution_2_example() {
-        let input = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-        assert_eq!(4, solution_2(input));
+    fn short_reports_are_safe() {
+        let rows = parse("5\n1 9").unwrap();
+
+        assert_eq!(1, solution_1(&rows));
+        assert_eq!(2, solution_2(&rows));
     }
 
     #[test]
     fn solution_2_real() {
-        let input = include_str!("input_files/day2.txt");
+        let input = input_or_skip!(crate::inputs().load(2));
 
-        assert_eq!(413, solution_2(input));
+        assert_eq!(413, solution_2(&parse(&input).unwrap()));
     }
 }
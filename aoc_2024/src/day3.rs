@@ -1,3 +1,4 @@
+use aoc_common::{Answer, ParseError, Solution, SolveError};
 use regex::Regex;
 
 fn multiply(input: &str) -> u32 {
@@ -14,11 +15,15 @@ fn multiply(input: &str) -> u32 {
         .fold(0, |acc, (first, second)| acc + (first * second))
 }
 
-fn solution_1(input: &str) -> u32 {
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    Ok(input.trim())
+}
+
+pub fn solution_1(input: &str) -> u32 {
     multiply(input)
 }
 
-fn solution_2(input: &str) -> u32 {
+pub fn solution_2(input: &str) -> u32 {
     let replace_re = Regex::new("don't\\(\\)[\\s\\S]*?do\\(\\)").unwrap();
 
     let input = replace_re.replace_all(input, "");
@@ -26,35 +31,46 @@ fn solution_2(input: &str) -> u32 {
     multiply(&input)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day3;
 
-    #[test]
-    fn solution_1_example() {
-        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+impl Solution for Day3 {
+    const YEAR: u16 = 2024;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+
+    type Params = ();
+    type Input<'a> = &'a str;
 
-        assert_eq!(161, solution_1(input));
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
     }
 
-    #[test]
-    fn solution_1_real() {
-        let input = include_str!("input_files/day3.txt");
+    fn part1(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_1(input).into())
+    }
 
-        assert_eq!(188192787, solution_1(input));
+    fn part2(input: &Self::Input<'_>, _: &()) -> Result<Answer, SolveError> {
+        Ok(solution_2(input).into())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::input_or_skip;
+
+    use super::*;
 
     #[test]
-    fn solution_2_example() {
-        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    fn solution_1_real() {
+        let input = input_or_skip!(crate::inputs().load(3));
 
-        assert_eq!(48, solution_2(input));
+        assert_eq!(188192787, solution_1(parse(&input).unwrap()));
     }
 
     #[test]
     fn solution_2_real() {
-        let input = include_str!("input_files/day3.txt");
+        let input = input_or_skip!(crate::inputs().load(3));
 
-        assert_eq!(113965544, solution_2(input));
+        assert_eq!(113965544, solution_2(parse(&input).unwrap()));
     }
 }
@@ -1,4 +1,24 @@
 #![allow(dead_code)]
+
+use aoc_common::{Inputs, Puzzle};
+
 pub mod day1;
 pub mod day2;
 pub mod day3;
+
+pub static SOLUTIONS: &[&dyn Puzzle] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+];
+
+aoc_common::example_tests! {
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+}
+
+/// This year's personal puzzle inputs, read from `src/input_files` unless `AOC_INPUT_DIR` is set.
+pub fn inputs() -> Inputs {
+    Inputs::for_year(2024, concat!(env!("CARGO_MANIFEST_DIR"), "/src/input_files"))
+}
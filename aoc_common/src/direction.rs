@@ -0,0 +1,227 @@
+use std::{fmt, str::FromStr};
+
+use crate::point::{Point2, Signed};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirError(String);
+
+impl fmt::Display for ParseDirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid direction `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirError {}
+
+/// One of the four orthogonal directions.
+///
+/// Offsets follow the screen convention used by [`Grid`](crate::Grid): `y` grows downwards, so
+/// `Up` is `(0, -1)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Every direction, clockwise from `Up`.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn offset<T: Signed>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+
+        match self {
+            Dir4::Up => Point2::new(zero, -one),
+            Dir4::Right => Point2::new(one, zero),
+            Dir4::Down => Point2::new(zero, one),
+            Dir4::Left => Point2::new(-one, zero),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The arrow pointing this way: `^`, `>`, `v` or `<`.
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+
+    /// Parses any of the usual encodings: arrows (`^v<>`), `UDLR` or compass points (`NSEW`),
+    /// in either case.
+    pub fn from_char(char: char) -> Option<Self> {
+        match char.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Some(Dir4::Up),
+            '>' | 'R' | 'E' => Some(Dir4::Right),
+            'V' | 'D' | 'S' => Some(Dir4::Down),
+            '<' | 'L' | 'W' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = ParseDirError;
+
+    fn try_from(char: char) -> Result<Self, Self::Error> {
+        Self::from_char(char).ok_or_else(|| ParseDirError(char.to_string()))
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(char), None) => Self::from_char(char),
+            _ => None,
+        }
+        .ok_or_else(|| ParseDirError(s.to_string()))
+    }
+}
+
+/// One of the eight compass directions, diagonals included, with the same offsets as [`Dir4`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// Every direction, clockwise from `North`.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn offset<T: Signed>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+
+        match self {
+            Dir8::North => Point2::new(zero, -one),
+            Dir8::NorthEast => Point2::new(one, -one),
+            Dir8::East => Point2::new(one, zero),
+            Dir8::SouthEast => Point2::new(one, one),
+            Dir8::South => Point2::new(zero, one),
+            Dir8::SouthWest => Point2::new(-one, one),
+            Dir8::West => Point2::new(-one, zero),
+            Dir8::NorthWest => Point2::new(-one, -one),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::North,
+            Dir4::Right => Dir8::East,
+            Dir4::Down => Dir8::South,
+            Dir4::Left => Dir8::West,
+        }
+    }
+}
+
+/// Parses a single [`Dir4`] character, or a pair of them for the diagonals such as `NE` or `UL`.
+impl FromStr for Dir8 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dirs = s.chars().map(Dir4::from_char).collect::<Option<Vec<_>>>();
+
+        let dir = match dirs.as_deref() {
+            Some(&[dir]) => Some(dir.into()),
+            Some(&[Dir4::Up, Dir4::Right]) => Some(Dir8::NorthEast),
+            Some(&[Dir4::Down, Dir4::Right]) => Some(Dir8::SouthEast),
+            Some(&[Dir4::Down, Dir4::Left]) => Some(Dir8::SouthWest),
+            Some(&[Dir4::Up, Dir4::Left]) => Some(Dir8::NorthWest),
+            _ => None,
+        };
+
+        dir.ok_or_else(|| ParseDirError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_common_encodings() {
+        for encoding in ["^>v<", "URDL", "NESW", "nesw"] {
+            let dirs = encoding
+                .chars()
+                .map(Dir4::try_from)
+                .collect::<Result<Vec<_>, _>>();
+
+            assert_eq!(Ok(Dir4::ALL.to_vec()), dirs);
+        }
+
+        for dir in Dir4::ALL {
+            assert_eq!(Some(dir), Dir4::from_char(dir.arrow()));
+        }
+
+        assert_eq!(Ok(Dir4::Left), "L".parse());
+        assert_eq!(Err(ParseDirError("X".to_string())), "X".parse::<Dir4>());
+        assert!("UD".parse::<Dir4>().is_err());
+    }
+
+    #[test]
+    fn parse_diagonals() {
+        assert_eq!(Ok(Dir8::NorthEast), "NE".parse());
+        assert_eq!(Ok(Dir8::SouthWest), "DL".parse());
+        assert_eq!(Ok(Dir8::West), "W".parse());
+        assert!("NS".parse::<Dir8>().is_err());
+    }
+
+    #[test]
+    fn offsets_and_turns() {
+        assert_eq!(Point2::new(0, -1), Dir4::Up.offset::<i32>());
+        assert_eq!(Point2::new(-1, 1), Dir8::SouthWest.offset::<i64>());
+        assert_eq!(Dir4::Up, Dir4::Left.turn_right());
+        assert_eq!(Dir4::Down, Dir4::Left.turn_left());
+        assert_eq!(Dir4::Right, Dir4::Left.opposite());
+        assert_eq!(Dir8::NorthWest, Dir8::North.turn_left());
+        assert_eq!(Dir8::SouthEast, Dir8::NorthWest.opposite());
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+
+use toml::{Table, Value};
+
+use crate::{Part, Puzzle};
+
+/// One example from a puzzle's text, read from the day's fixture file.
+///
+/// A fixture holds one table per example, named after it:
+///
+/// ```toml
+/// [example]
+/// part1 = 24000
+/// part2 = 45000
+/// input = '''
+/// 1000
+/// 2000
+/// '''
+///
+/// [smaller]
+/// params = { row = 10 }
+/// part1 = 26
+/// input = "..."
+/// ```
+///
+/// Parts without an answer are not checked, and `"no answer"` expects the part to find none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    /// Overrides of the puzzle's parameters, e.g. a smaller grid than the real input's.
+    pub params: Vec<(String, String)>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Example {
+    pub fn answer(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureError(String);
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Reads every example of a fixture file, in the order of their names.
+pub fn parse_examples(fixture: &str) -> Result<Vec<Example>, FixtureError> {
+    let examples = fixture
+        .parse::<Table>()
+        .map_err(|err| FixtureError(err.to_string()))?;
+
+    examples
+        .into_iter()
+        .map(|(name, example)| {
+            let Value::Table(mut example) = example else {
+                return Err(FixtureError(format!("`{name}` is not a table")));
+            };
+
+            let input = match example.remove("input") {
+                Some(Value::String(input)) => input,
+                _ => return Err(FixtureError(format!("`{name}` has no `input` string"))),
+            };
+
+            let params = match example.remove("params") {
+                Some(Value::Table(params)) => params
+                    .iter()
+                    .map(|(name, value)| (name.clone(), text(value)))
+                    .collect(),
+                Some(_) => return Err(FixtureError(format!("`{name}.params` is not a table"))),
+                None => vec![],
+            };
+
+            let part1 = example.remove("part1").as_ref().map(text);
+            let part2 = example.remove("part2").as_ref().map(text);
+
+            if let Some(key) = example.keys().next() {
+                return Err(FixtureError(format!("`{name}` has an unknown key `{key}`")));
+            }
+
+            Ok(Example {
+                name,
+                input,
+                params,
+                part1,
+                part2,
+            })
+        })
+        .collect()
+}
+
+/// Solves every example of `fixture` and panics listing each answer that does not match.
+///
+/// Answers are compared as text with surrounding whitespace trimmed, like `aoc verify` does.
+pub fn check_examples(puzzle: &dyn Puzzle, fixture: &str) {
+    let examples = match parse_examples(fixture) {
+        Ok(examples) => examples,
+        Err(err) => panic!("invalid fixture for day {}: {err}", puzzle.day()),
+    };
+
+    let mut failures = vec![];
+
+    for example in &examples {
+        for part in Part::ALL {
+            let Some(expected) = example.answer(part) else {
+                continue;
+            };
+
+            let answer = match puzzle.solve(part, &example.input, &example.params) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    failures.push(format!("{} part {part}: {err}", example.name));
+                    continue;
+                }
+            };
+
+            if answer.to_string().trim() != expected.trim() {
+                failures.push(format!(
+                    "{} part {part}: expected {}, found {}",
+                    example.name,
+                    expected.trim(),
+                    answer.to_string().trim()
+                ));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} day {} examples failed:\n{}",
+            puzzle.year(),
+            puzzle.day(),
+            failures.join("\n")
+        );
+    }
+}
+
+/// Generates a test per day that checks the examples in `src/examples/dayN.toml`.
+///
+/// ```ignore
+/// aoc_common::example_tests! {
+///     day1::Day1,
+///     day2::Day2,
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($($day:ident::$solution:ident),* $(,)?) => {
+        #[cfg(test)]
+        mod example_tests {
+            $(
+                #[test]
+                fn $day() {
+                    $crate::example::check_examples(
+                        &super::$day::$solution,
+                        include_str!(concat!(
+                            env!("CARGO_MANIFEST_DIR"),
+                            "/src/examples/",
+                            stringify!($day),
+                            ".toml"
+                        )),
+                    );
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::tests::Length;
+
+    use super::*;
+
+    const FIXTURE: &str = "
+[example]
+part1 = 4
+part2 = \"))((\"
+input = '''
+(())
+'''
+
+[with_params]
+params = { offset = 2 }
+part1 = 4
+input = 'ab'
+";
+
+    #[test]
+    fn parse_named_examples() {
+        let examples = parse_examples(FIXTURE).unwrap();
+
+        assert_eq!(
+            Example {
+                name: "example".to_string(),
+                input: "(())\n".to_string(),
+                params: vec![],
+                part1: Some("4".to_string()),
+                part2: Some("))((".to_string()),
+            },
+            examples[0]
+        );
+        assert_eq!(
+            vec![("offset".to_string(), "2".to_string())],
+            examples[1].params
+        );
+        assert_eq!(None, examples[1].answer(Part::Two));
+    }
+
+    #[test]
+    fn reject_malformed_fixtures() {
+        assert_eq!(
+            Err(FixtureError("`example` has no `input` string".to_string())),
+            parse_examples("[example]\npart1 = 3")
+        );
+        assert_eq!(
+            Err(FixtureError(
+                "`example` has an unknown key `part3`".to_string()
+            )),
+            parse_examples("[example]\ninput = ''\npart3 = 3")
+        );
+    }
+
+    #[test]
+    fn matching_examples_pass() {
+        check_examples(&Length, FIXTURE);
+    }
+
+    #[test]
+    #[should_panic(expected = "example part 1: expected 5, found 4")]
+    fn wrong_answers_fail() {
+        check_examples(&Length, "[example]\ninput = '(())'\npart1 = 5");
+    }
+}
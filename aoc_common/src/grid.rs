@@ -0,0 +1,445 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{Dir4, Dir8, Point2};
+
+/// A cell position, with `(0, 0)` the top left corner.
+pub type Position = Point2<usize>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// A line had a different number of cells than the first one. Lines are numbered from 1.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character was not a valid cell. Lines and columns are numbered from 1.
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no cells"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expected {expected} like the first line"
+            ),
+            GridError::InvalidCell { line, column, cell } => {
+                write!(f, "invalid cell `{cell}` at line {line}, column {column}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A dense, rectangular grid of cells stored row by row.
+///
+/// `y` grows downwards, matching both the order of lines in the puzzle input and the offsets of
+/// [`Dir4`] and [`Dir8`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A `width` by `height` grid with every cell set to `fill`.
+    ///
+    /// Panics when the grid would have no cells, which [`Grid::from_rows`] rejects too.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        assert!(width > 0 && height > 0, "{}", GridError::Empty);
+
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from rows of cells, which must all be the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+
+        if width == 0 {
+            return Err(GridError::Empty);
+        }
+
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    line: index + 1,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+
+            cells.extend(row);
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses one row per line, converting each character with `cell`. Surrounding whitespace on
+    /// each line is ignored, so indented examples parse as-is.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, GridError> {
+        let rows = input
+            .trim()
+            .lines()
+            .enumerate()
+            .map(|(line, text)| {
+                text.trim()
+                    .chars()
+                    .enumerate()
+                    .map(|(column, char)| {
+                        cell(char).ok_or(GridError::InvalidCell {
+                            line: line + 1,
+                            column: column + 1,
+                            cell: char,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the position `offset` away from `position`, if it is inside the grid.
+    pub fn offset(&self, position: Position, offset: Point2<isize>) -> Option<Position> {
+        position
+            .checked_add_signed(offset)
+            .filter(|&position| self.contains(position))
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.y * self.width + position.x])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.y * self.width + position.x]),
+            false => None,
+        }
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The position of the first cell, row by row, matching `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The cells of row `y`, left to right. Panics when the row is outside of the grid, like
+    /// indexing does.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(
+            y < self.height,
+            "row {y} is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The cells of column `x`, top to bottom. Panics when the column is outside of the grid, like
+    /// indexing does.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// The cells met walking from `start` in steps of `step` until leaving the grid, not
+    /// including `start` itself.
+    pub fn ray(
+        &self,
+        start: Position,
+        step: Point2<isize>,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        std::iter::successors(self.offset(start, step), move |&position| {
+            self.offset(position, step)
+        })
+        .map(|position| (position, &self[position]))
+    }
+
+    /// The up to 4 orthogonal neighbours of `position` inside the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(position, dir.offset()))
+    }
+
+    /// The up to 8 neighbours of `position` inside the grid, diagonals included.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.offset(position, dir.offset()))
+    }
+
+    /// Every position on the border of the grid, each once, row by row.
+    pub fn edges(&self) -> impl Iterator<Item = Position> + '_ {
+        let last_x = self.width.saturating_sub(1);
+        let last_y = self.height.saturating_sub(1);
+
+        self.positions()
+            .filter(move |&Point2 { x, y }| y == 0 || y == last_y || x == 0 || x == last_x)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {position} is outside of the {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("position {position} is outside of the {width}x{height} grid"),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Some)
+    }
+}
+
+/// Prints each row on its own line, with no separator between cells.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, |char| char.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parse_indented_text() {
+        let grid = digits(
+            "123
+            456",
+        );
+
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&4), grid.get(Point2::new(0, 1)));
+        assert_eq!(6, grid[Point2::new(2, 1)]);
+        assert_eq!(None, grid.get(Point2::new(3, 0)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let digit = |char: char| char.to_digit(10);
+
+        assert_eq!(
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            }),
+            Grid::parse("123\n45", digit)
+        );
+        assert_eq!(
+            Err(GridError::InvalidCell {
+                line: 2,
+                column: 2,
+                cell: 'x'
+            }),
+            Grid::parse("123\n4x6", digit)
+        );
+        assert_eq!(Err(GridError::Empty), Grid::parse("", digit));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the 3x2 grid")]
+    fn index_out_of_bounds() {
+        let grid = digits("123\n456");
+
+        let _ = grid[Point2::new(0, 2)];
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = digits("123\n456");
+
+        assert_eq!(&[4, 5, 6], grid.row(1));
+        assert_eq!(vec![&2, &5], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(2, grid.rows().count());
+        assert_eq!(Some(Point2::new(1, 1)), grid.position(|&cell| cell == 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside of the 3x2 grid")]
+    fn row_out_of_bounds() {
+        digits("123\n456").row(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside of the 3x2 grid")]
+    fn column_out_of_bounds() {
+        let _ = digits("123\n456").column(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "grid has no cells")]
+    fn new_rejects_empty_grids() {
+        Grid::new(0, 2, 0);
+    }
+
+    #[test]
+    fn rays_stop_at_the_border() {
+        let grid = digits("123\n456\n789");
+
+        let right = grid
+            .ray(Point2::new(0, 1), Point2::new(1, 0))
+            .map(|(_, &cell)| cell);
+        let up_left = grid
+            .ray(Point2::new(2, 2), Point2::new(-1, -1))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![5, 6], right.collect::<Vec<_>>());
+        assert_eq!(
+            vec![(Point2::new(1, 1), &5), (Point2::new(0, 0), &1)],
+            up_left
+        );
+        assert_eq!(0, grid.ray(Point2::new(0, 0), Point2::new(0, -1)).count());
+    }
+
+    #[test]
+    fn neighbours_inside_the_grid() {
+        let grid = digits("123\n456\n789");
+
+        assert_eq!(
+            vec![Point2::new(1, 0), Point2::new(0, 1)],
+            grid.neighbours4(Point2::new(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(4, grid.neighbours4(Point2::new(1, 1)).count());
+        assert_eq!(3, grid.neighbours8(Point2::new(2, 2)).count());
+        assert_eq!(8, grid.neighbours8(Point2::new(1, 1)).count());
+    }
+
+    #[test]
+    fn edges_are_visited_once() {
+        let grid = digits("123\n456\n789");
+
+        let edges = grid
+            .edges()
+            .map(|position| grid[position])
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![1, 2, 3, 4, 6, 7, 8, 9], edges);
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let grid = "#.#\n.#.".parse::<Grid<char>>().unwrap();
+
+        assert_eq!("#.#\n.#.", grid.to_string());
+        assert_eq!(
+            "101\n010",
+            grid.map(|&cell| (cell == '#') as u8).to_string()
+        );
+    }
+}
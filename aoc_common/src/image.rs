@@ -0,0 +1,152 @@
+use std::{fs, io, path::Path};
+
+use crate::{grid::Position, Grid};
+
+pub type Rgb = [u8; 3];
+
+/// A picture to look at puzzles with, saved as PPM or PNG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// Row by row, from the top left.
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// Draws each cell of `grid` as a pixel of the colour `colour` picks for it.
+    pub fn from_grid<T>(grid: &Grid<T>, mut colour: impl FnMut(&T) -> Rgb) -> Self {
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            pixels: grid.values().map(&mut colour).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<Rgb> {
+        (position.x < self.width && position.y < self.height)
+            .then(|| self.pixels[position.y * self.width + position.x])
+    }
+
+    /// Colours a pixel, ignoring pixels outside the image.
+    pub fn set(&mut self, position: Position, colour: Rgb) {
+        if position.x < self.width && position.y < self.height {
+            self.pixels[position.y * self.width + position.x] = colour;
+        }
+    }
+
+    /// Blows every pixel up into a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let (width, height) = (self.width * factor, self.height * factor);
+
+        Self {
+            width,
+            height,
+            pixels: (0..width * height)
+                .map(|index| {
+                    let (x, y) = (index % width / factor, index / width / factor);
+                    self.pixels[y * self.width + x]
+                })
+                .collect(),
+        }
+    }
+
+    /// Encodes the image as a binary PPM, which almost anything can open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+
+        ppm
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = vec![];
+        let mut encoder = png::Encoder::new(&mut png, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        // Writing to memory can only fail on images too large for PNG.
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels.concat()))
+            .expect("image too large for PNG");
+
+        png
+    }
+
+    /// Saves the image as a PNG, or as a PPM if `path` ends in `.ppm`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let bytes = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            _ => self.to_png(),
+        };
+
+        fs::write(path, bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = [255, 0, 0];
+    const BLACK: Rgb = [0, 0, 0];
+
+    fn checkers() -> Image {
+        let grid = Grid::from_rows(vec![vec![true, false], vec![false, true]]).unwrap();
+
+        Image::from_grid(&grid, |&lit| match lit {
+            true => RED,
+            false => BLACK,
+        })
+    }
+
+    #[test]
+    fn draw_and_scale() {
+        let mut image = checkers().scaled(2);
+
+        assert_eq!((4, 4), (image.width(), image.height()));
+        assert_eq!(Some(RED), image.get(Position::new(1, 1)));
+        assert_eq!(Some(BLACK), image.get(Position::new(2, 1)));
+        assert_eq!(None, image.get(Position::new(4, 0)));
+
+        image.set(Position::new(2, 1), RED);
+        image.set(Position::new(9, 9), RED);
+
+        assert_eq!(Some(RED), image.get(Position::new(2, 1)));
+    }
+
+    #[test]
+    fn encode_ppm_and_png() {
+        let image = checkers();
+
+        assert_eq!(
+            b"P6\n2 2\n255\n\xff\x00\x00\x00\x00\x00\x00\x00\x00\xff\x00\x00".to_vec(),
+            image.to_ppm()
+        );
+
+        let png = image.to_png();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+
+        assert_eq!((2, 2), (info.width, info.height));
+        assert_eq!(image.pixels.concat(), pixels);
+    }
+}
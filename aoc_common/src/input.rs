@@ -0,0 +1,139 @@
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Overrides where puzzle inputs are read from. Inputs for each year are then expected in a
+/// subdirectory named after the year, e.g. `$AOC_INPUT_DIR/2022/day7.txt`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing(PathBuf),
+    Unreadable(PathBuf, io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing(path) => write!(
+                f,
+                "no puzzle input at {} (add the file or set {INPUT_DIR_VAR})",
+                path.display()
+            ),
+            InputError::Unreadable(path, err) => {
+                write!(f, "could not read {}: {err}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The directory holding one year's personal puzzle inputs, named `day1.txt`, `day2.txt`, ...
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inputs {
+    dir: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Uses `$AOC_INPUT_DIR/<year>` when the variable is set, and `default_dir` otherwise.
+    pub fn for_year(year: u16, default_dir: impl Into<PathBuf>) -> Self {
+        match env::var_os(INPUT_DIR_VAR) {
+            Some(root) => Self::new(PathBuf::from(root).join(year.to_string())),
+            None => Self::new(default_dir),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day}.txt"))
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        let path = self.path(day);
+
+        fs::read_to_string(&path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => InputError::Missing(path),
+            _ => InputError::Unreadable(path, err),
+        })
+    }
+}
+
+/// Reports a test as skipped along with the reason.
+///
+/// The test harness only captures output written through `print!` and `eprint!`, so writing to
+/// stderr directly keeps the message visible without `--nocapture`.
+pub fn report_skipped(reason: &dyn fmt::Display) {
+    use std::io::Write;
+
+    let thread = std::thread::current();
+    let test = thread.name().unwrap_or("test");
+
+    let _ = writeln!(io::stderr(), "{test} skipped: {reason}");
+}
+
+/// Evaluates to the loaded input, or reports the test as skipped and returns early when the input
+/// could not be loaded.
+///
+/// Returning early means the harness counts the test as passed; only the line printed by
+/// [`report_skipped`] tells it apart.
+///
+/// ```ignore
+/// let input = input_or_skip!(crate::inputs().load(7));
+/// ```
+#[macro_export]
+macro_rules! input_or_skip {
+    ($input:expr) => {
+        match $input {
+            Ok(input) => input,
+            Err(err) => {
+                $crate::input::report_skipped(&err);
+                return;
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_for_day() {
+        let inputs = Inputs::new("inputs/2022");
+
+        assert_eq!(PathBuf::from("inputs/2022/day7.txt"), inputs.path(7));
+    }
+
+    #[test]
+    fn load_existing_input() {
+        let dir = env::temp_dir().join(format!("aoc_common_input_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day1.txt"), "(())").unwrap();
+
+        let inputs = Inputs::new(&dir);
+
+        assert_eq!("(())", inputs.load(1).unwrap());
+        assert!(matches!(inputs.load(2), Err(InputError::Missing(_))));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_input_message() {
+        let error = InputError::Missing(PathBuf::from("input/day3.txt"));
+
+        assert_eq!(
+            "no puzzle input at input/day3.txt (add the file or set AOC_INPUT_DIR)",
+            error.to_string()
+        );
+    }
+}
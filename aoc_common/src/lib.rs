@@ -0,0 +1,20 @@
+pub mod direction;
+pub mod example;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod solution;
+pub mod vm;
+
+pub use direction::{Dir4, Dir8, ParseDirError};
+pub use example::{Example, FixtureError};
+pub use grid::{Grid, GridError};
+pub use image::Image;
+pub use input::{InputError, Inputs};
+pub use ocr::OcrError;
+pub use parse::ParseError;
+pub use point::{Point2, Point3};
+pub use solution::{Answer, ParamError, Params, Part, Puzzle, Solution, SolveError, Timings};
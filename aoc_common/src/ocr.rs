@@ -0,0 +1,167 @@
+use std::fmt;
+
+use crate::{Grid, Point2};
+
+pub const GLYPH_WIDTH: usize = 4;
+pub const GLYPH_HEIGHT: usize = 6;
+
+/// Letters are drawn every 5 columns, leaving a blank column between them.
+const GLYPH_STRIDE: usize = GLYPH_WIDTH + 1;
+
+/// The 4x6 letters puzzles draw their answers with. Letters the puzzles never use, or that do not
+/// fit in 4 columns, are missing.
+const FONT: &[(char, &str)] = &[
+    ('A', ".##. #..# #..# #### #..# #..#"),
+    ('B', "###. #..# ###. #..# #..# ###."),
+    ('C', ".##. #..# #... #... #..# .##."),
+    ('E', "#### #... ###. #... #... ####"),
+    ('F', "#### #... ###. #... #... #..."),
+    ('G', ".##. #..# #... #.## #..# .###"),
+    ('H', "#..# #..# #### #..# #..# #..#"),
+    ('I', ".### ..#. ..#. ..#. ..#. .###"),
+    ('J', "..## ...# ...# ...# #..# .##."),
+    ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+    ('L', "#... #... #... #... #... ####"),
+    ('O', ".##. #..# #..# #..# #..# .##."),
+    ('P', "###. #..# #..# ###. #... #..."),
+    ('R', "###. #..# #..# ###. #.#. #..#"),
+    ('S', ".### #... #... .##. ...# ###."),
+    ('U', "#..# #..# #..# #..# #..# .##."),
+    ('Z', "#### ...# ..#. .#.. #... ####"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// The screen is not 6 pixels high, or not wide enough for a whole number of letters.
+    Size { width: usize, height: usize },
+    /// The letter at `index`, counting from 0, is not in the font. `glyph` is its picture, rows
+    /// separated by spaces.
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Size { width, height } => {
+                write!(f, "a {width}x{height} screen does not hold 4x6 letters")
+            }
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "letter {} is not a known glyph: `{glyph}`", index + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Draws the glyph at column `x` in the same form as the font.
+fn glyph(pixels: &Grid<bool>, x: usize) -> String {
+    (0..GLYPH_HEIGHT)
+        .map(|y| {
+            (x..x + GLYPH_WIDTH)
+                .map(|x| match pixels[Point2::new(x, y)] {
+                    true => '#',
+                    false => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads the letters drawn on a screen of lit pixels.
+pub fn read_letters(pixels: &Grid<bool>) -> Result<String, OcrError> {
+    let (width, height) = (pixels.width(), pixels.height());
+
+    // The last letter may or may not be followed by its blank column.
+    if height != GLYPH_HEIGHT || (width + 1) % GLYPH_STRIDE > 1 || width < GLYPH_WIDTH {
+        return Err(OcrError::Size { width, height });
+    }
+
+    (0..(width + 1) / GLYPH_STRIDE)
+        .map(|index| {
+            let glyph = glyph(pixels, index * GLYPH_STRIDE);
+
+            FONT.iter()
+                .find(|(_, drawn)| *drawn == glyph)
+                .map(|&(letter, _)| letter)
+                .ok_or(OcrError::UnknownGlyph { index, glyph })
+        })
+        .collect()
+}
+
+/// Reads the letters of a screen drawn as text, with `#` or `█` for lit pixels and anything else
+/// for dark ones.
+pub fn read_text(screen: &str) -> Result<String, OcrError> {
+    let rows = screen
+        .lines()
+        .map(|line| line.chars().map(|char| matches!(char, '#' | '█')).collect())
+        .collect::<Vec<Vec<bool>>>();
+
+    // Lines may have lost their trailing dark pixels, so pad them to the widest.
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            row.resize(width, false);
+            row
+        })
+        .collect();
+
+    match Grid::from_rows(rows) {
+        Ok(pixels) => read_letters(&pixels),
+        Err(_) => Err(OcrError::Size { width, height: 0 }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_every_letter() {
+        let letters = FONT.iter().map(|&(letter, _)| letter).collect::<String>();
+        let screen = (0..GLYPH_HEIGHT)
+            .map(|y| {
+                FONT.iter()
+                    .map(|(_, glyph)| format!("{} ", glyph.split(' ').nth(y).unwrap()))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(Ok(letters), read_text(&screen));
+    }
+
+    #[test]
+    fn read_rendered_screen() {
+        let screen = "████ ████ ████  ██  █  █   ██  ██  ███
+█       █ █    █  █ █  █    █ █  █ █  █
+███    █  ███  █    ████    █ █  █ ███
+█     █   █    █    █  █    █ ████ █  █
+█    █    █    █  █ █  █ █  █ █  █ █  █
+████ ████ █     ██  █  █  ██  █  █ ███  ";
+
+        assert_eq!(Ok("EZFCHJAB".to_string()), read_text(screen));
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let screen = "#..#.\n#..#.\n####.\n#..#.\n#..#.\n#.##.";
+
+        assert_eq!(
+            Err(OcrError::UnknownGlyph {
+                index: 0,
+                glyph: "#..# #..# #### #..# #..# #.##".to_string()
+            }),
+            read_text(screen)
+        );
+        assert_eq!(
+            Err(OcrError::Size {
+                width: 4,
+                height: 2
+            }),
+            read_text("####\n####")
+        );
+    }
+}
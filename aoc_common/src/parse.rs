@@ -0,0 +1,244 @@
+use std::{fmt, str::FromStr};
+
+use nom::{
+    error::{VerboseError, VerboseErrorKind},
+    IResult,
+};
+
+use crate::grid::GridError;
+
+/// Malformed puzzle input, pointing at where in the input things went wrong.
+///
+/// Lines and columns are numbered from 1, counting characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, up to the end of its line. Empty when the input ended too early.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// An error for `text`, which must be a slice of `input`, with the line and column worked out
+    /// from where `text` starts.
+    pub fn at(input: &str, text: &str, message: impl Into<String>) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or(input.len());
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        let text = text.lines().next().unwrap_or_default();
+
+        Self::new(
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+            text,
+            message,
+        )
+    }
+
+    /// An error for input that ended where more was expected.
+    pub fn end_of(input: &str, message: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], message)
+    }
+
+    /// Converts the error of a nom parser run over `input`.
+    pub fn nom<'a, E: NomError<'a>>(input: &str, err: nom::Err<E>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => Self::end_of(input, "unexpected end of input"),
+            nom::Err::Error(err) | nom::Err::Failure(err) => {
+                let (text, message) = err.locate();
+                Self::at(input, text, message)
+            }
+        }
+    }
+}
+
+/// The nom errors [`ParseError::nom`] can point into the input with.
+pub trait NomError<'a> {
+    /// Where the error happened, and what went wrong there.
+    fn locate(self) -> (&'a str, String);
+}
+
+impl<'a> NomError<'a> for nom::error::Error<&'a str> {
+    fn locate(self) -> (&'a str, String) {
+        (
+            self.input,
+            format!("unexpected input ({})", self.code.description()),
+        )
+    }
+}
+
+/// Verbose errors report the innermost [`context`](nom::error::context) around the failure as the
+/// message, so parsers can say what they expected, e.g. ``context("expected `+` or `*`", ..)``.
+impl<'a> NomError<'a> for VerboseError<&'a str> {
+    fn locate(self) -> (&'a str, String) {
+        let context = self.errors.iter().find_map(|(input, kind)| match kind {
+            VerboseErrorKind::Context(context) => Some((*input, context.to_string())),
+            _ => None,
+        });
+
+        match context {
+            Some(context) => context,
+            None => match self.errors.into_iter().next() {
+                Some((input, VerboseErrorKind::Nom(kind))) => {
+                    (input, format!("unexpected input ({})", kind.description()))
+                }
+                Some((input, VerboseErrorKind::Char(char))) => {
+                    (input, format!("expected `{char}`"))
+                }
+                _ => ("", "unexpected input".to_string()),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )?;
+
+        match self.text.is_empty() {
+            true => Ok(()),
+            false => write!(f, ": `{}`", self.text),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<GridError> for ParseError {
+    fn from(err: GridError) -> Self {
+        match err {
+            GridError::Empty => Self::new(1, 1, "", "expected at least one cell"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => Self::new(
+                line,
+                expected.min(found) + 1,
+                "",
+                format!("expected {expected} cells like the first line, found {found}"),
+            ),
+            GridError::InvalidCell { line, column, cell } => {
+                Self::new(line, column, &cell.to_string(), "invalid cell")
+            }
+        }
+    }
+}
+
+/// Parses `text`, a slice of `input`, as a number.
+pub fn parse_number<T: FromStr>(input: &str, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::at(input, text, "expected a number"))
+}
+
+/// Runs a nom `parser` over the whole of `input`, allowing nothing but whitespace around it.
+pub fn parse_all<'a, T, E: NomError<'a>>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T, E>,
+) -> Result<T, ParseError> {
+    let (rest, parsed) = parser(input.trim_start()).map_err(|err| ParseError::nom(input, err))?;
+    let rest = rest.trim_start();
+
+    match rest.is_empty() {
+        true => Ok(parsed),
+        false => Err(ParseError::at(input, rest, "unexpected trailing input")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        character::complete::{char, digit1, u32},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    use super::*;
+
+    #[test]
+    fn locates_text_in_input() {
+        let input = "12\n3x4\n5";
+        let text = &input[4..];
+
+        assert_eq!(
+            ParseError::new(2, 2, "x4", "expected a digit"),
+            ParseError::at(input, text, "expected a digit")
+        );
+        assert_eq!(
+            "expected a digit at line 2, column 2: `x4`",
+            ParseError::at(input, text, "expected a digit").to_string()
+        );
+        assert_eq!(
+            "missing line at line 3, column 2",
+            ParseError::end_of(input, "missing line").to_string()
+        );
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let input = "10 ten";
+
+        assert_eq!(Ok(10), parse_number::<u32>(input, &input[..2]));
+        assert_eq!(
+            Err(ParseError::new(1, 4, "ten", "expected a number")),
+            parse_number::<u32>(input, &input[3..])
+        );
+    }
+
+    #[test]
+    fn nom_parsers_consume_all_input() {
+        fn numbers(input: &str) -> IResult<&str, Vec<&str>> {
+            separated_list1(char(','), digit1)(input)
+        }
+
+        assert_eq!(Ok(vec!["1", "22"]), parse_all("\n1,22\n", numbers));
+        assert_eq!(
+            Err(ParseError::new(1, 5, ";3", "unexpected trailing input")),
+            parse_all("1,22;3", numbers)
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 2, "x", "unexpected input (Digit)")),
+            parse_all("\n x", numbers)
+        );
+    }
+
+    #[test]
+    fn nom_contexts_become_messages() {
+        fn sum(input: &str) -> IResult<&str, (u32, u32), VerboseError<&str>> {
+            separated_pair(
+                u32,
+                context("expected `+`", char('+')),
+                context("expected a number", u32),
+            )(input)
+        }
+
+        assert_eq!(Ok((1, 2)), parse_all("1+2", sum));
+        assert_eq!(
+            Err(ParseError::new(1, 2, "-2", "expected `+`")),
+            parse_all("1-2", sum)
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 3, "x", "expected a number")),
+            parse_all("1+x", sum)
+        );
+    }
+}
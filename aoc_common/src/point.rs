@@ -0,0 +1,265 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// The integer types points can be built from.
+pub trait Number:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn distance(self, other: Self) -> Self {
+        match self > other {
+            true => self - other,
+            false => other - self,
+        }
+    }
+}
+
+pub trait Signed: Number + Neg<Output = Self> {
+    fn signum(self) -> Self {
+        match self.cmp(&Self::ZERO) {
+            Ordering::Less => -Self::ONE,
+            Ordering::Equal => Self::ZERO,
+            Ordering::Greater => Self::ONE,
+        }
+    }
+}
+
+macro_rules! impl_number {
+    ($($ty:ty),*) => {
+        $(
+            impl Number for $ty {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+            }
+        )*
+    };
+}
+
+impl_number!(i32, i64, i128, isize, u8, u32, u64, usize);
+
+impl Signed for i32 {}
+impl Signed for i64 {}
+impl Signed for i128 {}
+impl Signed for isize {}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Number> Point2<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO);
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+}
+
+impl<T: Signed> Point2<T> {
+    /// The unit step in each axis towards the sign of the coordinate, e.g. `(-4, 0)` becomes
+    /// `(-1, 0)`.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Moves one step, diagonals included, towards `target`.
+    pub fn step_towards(self, target: Self) -> Self {
+        self + (target - self).signum()
+    }
+}
+
+impl Point2<usize> {
+    /// Adds a signed offset, unless that would take either coordinate below zero.
+    pub fn checked_add_signed(self, offset: Point2<isize>) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Number> Point3<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO, T::ZERO);
+
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        self.x
+            .distance(other.x)
+            .max(self.y.distance(other.y))
+            .max(self.z.distance(other.z))
+    }
+}
+
+impl<T: Signed> Point3<T> {
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    pub fn step_towards(self, target: Self) -> Self {
+        self + (target - self).signum()
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($axis:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($axis: self.$axis + rhs.$axis),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($axis: self.$axis - rhs.$axis),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                Self { $($axis: self.$axis * rhs),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($axis: -self.$axis),* }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point2::new(3, -2);
+
+        assert_eq!(Point2::new(4, 0), point + Point2::new(1, 2));
+        assert_eq!(Point2::new(2, -4), point - Point2::new(1, 2));
+        assert_eq!(Point2::new(9, -6), point * 3);
+        assert_eq!(Point2::new(-3, 2), -point);
+
+        point += Point2::new(1, 1);
+        point -= Point2::new(0, 2);
+
+        assert_eq!(Point2::new(4, -3), point);
+        assert_eq!(Point3::new(2, 4, 6), Point3::new(1, 2, 3) * 2);
+    }
+
+    #[test]
+    fn distances() {
+        let from = Point2::new(8, 7);
+        let to = Point2::new(2, 10);
+
+        assert_eq!(9, from.manhattan(to));
+        assert_eq!(6, from.chebyshev(to));
+        assert_eq!(6, Point2::<usize>::ORIGIN.manhattan(Point2::new(2, 4)));
+        assert_eq!(6, Point3::new(1, 2, 3).manhattan(Point3::ORIGIN));
+        assert_eq!(3, Point3::new(1, 2, 3).chebyshev(Point3::ORIGIN));
+    }
+
+    #[test]
+    fn step_towards_target() {
+        assert_eq!(Point2::new(-1, 0), Point2::new(-4, 0).signum());
+        assert_eq!(
+            Point2::new(1, 1),
+            Point2::ORIGIN.step_towards(Point2::new(2, 1))
+        );
+        assert_eq!(
+            Point3::new(1, 0, -1),
+            Point3::ORIGIN.step_towards(Point3::new(5, 0, -2))
+        );
+    }
+
+    #[test]
+    fn add_signed_offsets() {
+        let point = Point2::new(1usize, 0);
+
+        assert_eq!(
+            Some(Point2::new(0, 1)),
+            point.checked_add_signed(Point2::new(-1, 1))
+        );
+        assert_eq!(None, point.checked_add_signed(Point2::new(0, -1)));
+    }
+}
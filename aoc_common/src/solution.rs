@@ -0,0 +1,435 @@
+use std::{
+    error::Error,
+    fmt,
+    hint::black_box,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::parse::ParseError;
+
+/// The result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// The solution ran to completion without finding an answer for the input.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Unsolved => write!(f, "no answer"),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, got `{s}`")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    Unknown(String),
+    Invalid { name: String, value: String },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Unknown(name) => write!(f, "unknown parameter `{name}`"),
+            ParamError::Invalid { name, value } => {
+                write!(f, "invalid value `{value}` for parameter `{name}`")
+            }
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+impl ParamError {
+    pub fn invalid(name: &str, value: &str) -> Self {
+        ParamError::Invalid {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Extra settings a puzzle needs besides its input, such as the row to scan in 2022 day 15.
+///
+/// `Default` provides the values for the author's puzzle input, and `set` lets tooling override
+/// them by name.
+pub trait Params: Default {
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+
+    fn values(&self) -> Vec<(&'static str, String)>;
+}
+
+/// Why a [`Puzzle`] could not be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Param(ParamError),
+    Parse(ParseError),
+    /// The input parsed, but solving it failed, such as when a value overflows.
+    Solve(String),
+}
+
+impl SolveError {
+    pub fn solve(err: impl fmt::Display) -> Self {
+        SolveError::Solve(err.to_string())
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Param(err) => write!(f, "{err}"),
+            SolveError::Parse(err) => write!(f, "invalid input: {err}"),
+            SolveError::Solve(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<ParamError> for SolveError {
+    fn from(err: ParamError) -> Self {
+        SolveError::Param(err)
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        SolveError::Parse(err)
+    }
+}
+
+impl Params for () {
+    fn set(&mut self, name: &str, _: &str) -> Result<(), ParamError> {
+        Err(ParamError::Unknown(name.to_string()))
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+}
+
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Params: Params;
+
+    /// The puzzle input once parsed, shared by both parts.
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part1(input: &Self::Input<'_>, params: &Self::Params) -> Result<Answer, SolveError>;
+
+    fn part2(input: &Self::Input<'_>, params: &Self::Params) -> Result<Answer, SolveError>;
+}
+
+/// How long a single run of each stage of a solution took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = black_box(f());
+
+    (result, start.elapsed())
+}
+
+/// Object safe view of a [`Solution`], used to keep every day of a year in a single registry.
+pub trait Puzzle: Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    /// The default parameters of the puzzle, by name.
+    fn params(&self) -> Vec<(&'static str, String)>;
+
+    /// Solves `part`, applying each `(name, value)` override on top of the default parameters.
+    fn solve(
+        &self,
+        part: Part,
+        input: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Answer, SolveError>;
+
+    /// Parses `input` and solves both parts once, timing each stage separately.
+    fn time(&self, input: &str, overrides: &[(String, String)]) -> Result<Timings, SolveError>;
+}
+
+fn params_with<P: Params>(overrides: &[(String, String)]) -> Result<P, ParamError> {
+    let mut params = P::default();
+
+    for (name, value) in overrides {
+        params.set(name, value)?;
+    }
+
+    Ok(params)
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        S::Params::default().values()
+    }
+
+    fn solve(
+        &self,
+        part: Part,
+        input: &str,
+        overrides: &[(String, String)],
+    ) -> Result<Answer, SolveError> {
+        let params = params_with::<S::Params>(overrides)?;
+        let input = S::parse(input)?;
+
+        match part {
+            Part::One => S::part1(&input, &params),
+            Part::Two => S::part2(&input, &params),
+        }
+    }
+
+    fn time(&self, input: &str, overrides: &[(String, String)]) -> Result<Timings, SolveError> {
+        let params = params_with::<S::Params>(overrides)?;
+
+        let (input, parse) = time(|| S::parse(input));
+        let input = input?;
+        let (answer, part1) = time(|| S::part1(&input, &params));
+        answer?;
+        let (answer, part2) = time(|| S::part2(&input, &params));
+        answer?;
+
+        Ok(Timings {
+            parse,
+            part1,
+            part2,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    pub(crate) struct Offset(i32);
+
+    impl Params for Offset {
+        fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError> {
+            match name {
+                "offset" => {
+                    self.0 = value
+                        .parse()
+                        .map_err(|_| ParamError::invalid(name, value))?
+                }
+                _ => return Err(ParamError::Unknown(name.to_string())),
+            }
+
+            Ok(())
+        }
+
+        fn values(&self) -> Vec<(&'static str, String)> {
+            vec![("offset", self.0.to_string())]
+        }
+    }
+
+    pub(crate) struct Length;
+
+    impl Solution for Length {
+        const YEAR: u16 = 2000;
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Length";
+
+        type Params = Offset;
+        type Input<'a> = &'a str;
+
+        fn parse(input: &str) -> Result<&str, ParseError> {
+            match input.trim() {
+                "" => Err(ParseError::end_of(input, "expected some text")),
+                input => Ok(input),
+            }
+        }
+
+        fn part1(input: &&str, params: &Self::Params) -> Result<Answer, SolveError> {
+            Ok((input.len() as i32 + params.0).into())
+        }
+
+        fn part2(input: &&str, _: &Self::Params) -> Result<Answer, SolveError> {
+            match input.starts_with('-') {
+                true => Err(SolveError::solve("cannot reverse a negative")),
+                false => Ok(input.chars().rev().collect::<String>().into()),
+            }
+        }
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!("42", Answer::from(42u64).to_string());
+        assert_eq!("CMZ", Answer::from("CMZ").to_string());
+        assert_eq!(Answer::Unsolved, Answer::from(None::<usize>));
+    }
+
+    #[test]
+    fn puzzle_uses_default_params() {
+        let puzzle: &dyn Puzzle = &Length;
+
+        assert_eq!(vec![("offset", "0".to_string())], puzzle.params());
+        assert_eq!(Ok(Answer::Number(3)), puzzle.solve(Part::One, "abc", &[]));
+        assert_eq!(
+            Ok(Answer::Text("cba".to_string())),
+            puzzle.solve(Part::Two, "abc", &[])
+        );
+    }
+
+    #[test]
+    fn puzzle_applies_overrides() {
+        let overrides = [("offset".to_string(), "10".to_string())];
+
+        assert_eq!(
+            Ok(Answer::Number(13)),
+            Length.solve(Part::One, "abc", &overrides)
+        );
+    }
+
+    #[test]
+    fn puzzle_times_with_overrides() {
+        let invalid = [("offset".to_string(), "ten".to_string())];
+
+        assert!(Length.time(" abc ", &[]).is_ok());
+        assert_eq!(
+            Err(ParamError::invalid("offset", "ten").into()),
+            Length.time(" abc ", &invalid)
+        );
+    }
+
+    #[test]
+    fn puzzle_reports_parse_errors() {
+        assert_eq!(
+            Err(SolveError::Parse(ParseError::new(
+                2,
+                1,
+                "",
+                "expected some text"
+            ))),
+            Length.solve(Part::One, " \n", &[])
+        );
+        assert!(matches!(Length.time("", &[]), Err(SolveError::Parse(_))));
+    }
+
+    #[test]
+    fn puzzle_reports_solve_errors() {
+        let err = Length.solve(Part::Two, "-abc", &[]).unwrap_err();
+
+        assert_eq!(
+            SolveError::Solve("cannot reverse a negative".to_string()),
+            err
+        );
+        assert_eq!("cannot reverse a negative", err.to_string());
+        assert_eq!(Ok(Answer::Number(4)), Length.solve(Part::One, "-abc", &[]));
+        assert_eq!(Err(err), Length.time("-abc", &[]));
+    }
+
+    #[test]
+    fn puzzle_rejects_bad_overrides() {
+        let unknown = [("row".to_string(), "10".to_string())];
+        let invalid = [("offset".to_string(), "ten".to_string())];
+
+        assert_eq!(
+            Err(ParamError::Unknown("row".to_string()).into()),
+            Length.solve(Part::One, "abc", &unknown)
+        );
+        assert_eq!(
+            Err(ParamError::invalid("offset", "ten").into()),
+            Length.solve(Part::One, "abc", &invalid)
+        );
+    }
+}
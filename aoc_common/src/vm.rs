@@ -0,0 +1,597 @@
+//! A small register machine for the puzzles that run made up assembly.
+//!
+//! Each puzzle describes its instructions in an [`InstructionSet`]: their names, the operands they
+//! take, how many cycles they last and what they do to the registers once those cycles are over.
+//! A [`Machine`] runs a program one cycle at a time as an iterator, and a [`Debugger`] adds
+//! breakpoints and a trace on top.
+
+use std::fmt::{self, Write};
+
+use crate::{parse::parse_number, ParseError};
+
+/// The kinds of operand an instruction takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperandKind {
+    Register,
+    Value,
+    /// Either a register or a value, as in `cpy 41 a` and `cpy b a`.
+    Any,
+}
+
+impl OperandKind {
+    fn placeholder(self) -> &'static str {
+        match self {
+            OperandKind::Register => "<register>",
+            OperandKind::Value => "<value>",
+            OperandKind::Any => "<register or value>",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// The index of a register in [`InstructionSet::registers`].
+    Register(usize),
+    Value(i64),
+}
+
+/// Where to go once an instruction is done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Next,
+    /// Jumps this many instructions forwards, or backwards when negative.
+    Jump(i64),
+}
+
+pub struct InstructionSpec {
+    pub name: &'static str,
+    pub operands: &'static [OperandKind],
+    /// How many cycles the instruction lasts. Its effect applies at the end of the last one.
+    pub cycles: u32,
+    pub execute: fn(&mut Registers, &[Operand]) -> Flow,
+}
+
+pub struct InstructionSet {
+    pub registers: &'static [&'static str],
+    pub instructions: &'static [InstructionSpec],
+}
+
+#[derive(Clone)]
+pub struct Instruction<'a> {
+    pub spec: &'a InstructionSpec,
+    /// As many as the spec lists.
+    operands: Vec<Operand>,
+}
+
+impl Instruction<'_> {
+    pub fn operands(&self) -> impl Iterator<Item = Operand> + '_ {
+        self.operands.iter().copied()
+    }
+
+    fn execute(&self, registers: &mut Registers) -> Flow {
+        (self.spec.execute)(registers, &self.operands)
+    }
+}
+
+impl fmt::Debug for Instruction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Instruction")
+            .field("name", &self.spec.name)
+            .field("operands", &self.operands().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl InstructionSet {
+    pub fn register(&self, name: &str) -> Option<usize> {
+        self.registers.iter().position(|&register| register == name)
+    }
+
+    fn spec(&self, name: &str) -> Option<&InstructionSpec> {
+        self.instructions
+            .iter()
+            .find(|spec| spec.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Lists the instructions for error messages, e.g. ``expected `noop` or `addx <value>` ``.
+    fn expected(&self) -> String {
+        let forms = self
+            .instructions
+            .iter()
+            .map(|spec| {
+                let operands = spec.operands.iter().map(|kind| kind.placeholder());
+
+                format!(
+                    "`{}`",
+                    [spec.name]
+                        .into_iter()
+                        .chain(operands)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )
+            })
+            .collect::<Vec<_>>();
+
+        match forms.split_last() {
+            Some((last, [])) => format!("expected {last}"),
+            Some((last, rest)) => format!("expected {} or {last}", rest.join(", ")),
+            None => "expected no instructions".to_string(),
+        }
+    }
+
+    fn operand(&self, input: &str, text: &str, kind: OperandKind) -> Result<Operand, ParseError> {
+        if let (OperandKind::Register | OperandKind::Any, Some(index)) = (kind, self.register(text))
+        {
+            return Ok(Operand::Register(index));
+        }
+
+        match kind {
+            OperandKind::Register => Err(ParseError::at(input, text, "expected a register")),
+            OperandKind::Value | OperandKind::Any => Ok(Operand::Value(parse_number(input, text)?)),
+        }
+    }
+
+    /// Parses one instruction per line, matching names regardless of case.
+    pub fn parse<'a>(&'a self, input: &str) -> Result<Vec<Instruction<'a>>, ParseError> {
+        input
+            .trim()
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                let words = line.split_whitespace().collect::<Vec<_>>();
+
+                let spec = words
+                    .split_first()
+                    .and_then(|(name, operands)| {
+                        self.spec(name)
+                            .filter(|spec| spec.operands.len() == operands.len())
+                    })
+                    .ok_or_else(|| ParseError::at(input, line, self.expected()))?;
+
+                let operands = words[1..]
+                    .iter()
+                    .zip(spec.operands)
+                    .map(|(text, &kind)| self.operand(input, text, kind))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Instruction { spec, operands })
+            })
+            .collect()
+    }
+
+    /// Writes an instruction back as text, with registers by name.
+    pub fn format(&self, instruction: &Instruction) -> String {
+        let mut text = instruction.spec.name.to_string();
+
+        for operand in instruction.operands() {
+            let _ = match operand {
+                Operand::Register(index) => write!(text, " {}", self.registers[index]),
+                Operand::Value(value) => write!(text, " {value}"),
+            };
+        }
+
+        text
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registers {
+    values: Vec<i64>,
+}
+
+impl Registers {
+    pub fn new(count: usize) -> Self {
+        Self {
+            values: vec![0; count],
+        }
+    }
+
+    pub fn get(&self, register: usize) -> i64 {
+        self.values[register]
+    }
+
+    pub fn set(&mut self, register: usize, value: i64) {
+        self.values[register] = value;
+    }
+
+    /// The value of an operand: the register's value, or the value itself.
+    pub fn value(&self, operand: Operand) -> i64 {
+        match operand {
+            Operand::Register(register) => self.get(register),
+            Operand::Value(value) => value,
+        }
+    }
+
+    pub fn values(&self) -> &[i64] {
+        &self.values
+    }
+}
+
+/// The state of the machine during one cycle, before the effect of an instruction finishing in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tick {
+    /// Counted from 1.
+    pub cycle: u64,
+    /// The index of the running instruction.
+    pub pc: usize,
+    pub registers: Registers,
+}
+
+/// Runs a program, yielding a [`Tick`] per cycle until the program counter leaves the program.
+pub struct Machine<'a> {
+    set: &'a InstructionSet,
+    program: &'a [Instruction<'a>],
+    registers: Registers,
+    pc: usize,
+    cycle: u64,
+    /// How many cycles the running instruction has already taken.
+    elapsed: u32,
+}
+
+impl<'a> Machine<'a> {
+    pub fn new(set: &'a InstructionSet, program: &'a [Instruction<'a>]) -> Self {
+        Self {
+            set,
+            program,
+            registers: Registers::new(set.registers.len()),
+            pc: 0,
+            cycle: 0,
+            elapsed: 0,
+        }
+    }
+
+    /// Starts with `register` set to `value`.
+    ///
+    /// Panics if the instruction set has no such register.
+    pub fn with_register(mut self, register: &str, value: i64) -> Self {
+        let index = self
+            .set
+            .register(register)
+            .unwrap_or_else(|| panic!("there is no register `{register}`"));
+
+        self.registers.set(index, value);
+        self
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn register(&self, name: &str) -> Option<i64> {
+        Some(self.registers.get(self.set.register(name)?))
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    /// Runs one cycle.
+    pub fn step(&mut self) -> Option<Tick> {
+        let instruction = self.program.get(self.pc)?;
+
+        self.cycle += 1;
+        self.elapsed += 1;
+
+        let tick = Tick {
+            cycle: self.cycle,
+            pc: self.pc,
+            registers: self.registers.clone(),
+        };
+
+        if self.elapsed >= instruction.spec.cycles {
+            self.elapsed = 0;
+            self.pc = match instruction.execute(&mut self.registers) {
+                Flow::Next => self.pc + 1,
+                Flow::Jump(offset) => (self.pc as i64 + offset).try_into().unwrap_or(usize::MAX),
+            };
+        }
+
+        Some(tick)
+    }
+}
+
+impl Iterator for Machine<'_> {
+    type Item = Tick;
+
+    fn next(&mut self) -> Option<Tick> {
+        self.step()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stops once the cycle has run.
+    Cycle(u64),
+    /// Stops at each cycle during which the register holds the value after holding another one
+    /// the cycle before, or from the start.
+    Register { register: usize, value: i64 },
+}
+
+impl Breakpoint {
+    fn hit(&self, previous: Option<&Tick>, tick: &Tick) -> bool {
+        match *self {
+            Breakpoint::Cycle(cycle) => tick.cycle == cycle,
+            Breakpoint::Register { register, value } => {
+                tick.registers.get(register) == value
+                    && previous.is_none_or(|previous| previous.registers.get(register) != value)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stop {
+    Breakpoint(Breakpoint, Tick),
+    Halted,
+}
+
+/// Runs a machine with breakpoints, keeping a trace of every cycle run.
+pub struct Debugger<'a> {
+    machine: Machine<'a>,
+    breakpoints: Vec<Breakpoint>,
+    trace: Vec<Tick>,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(machine: Machine<'a>) -> Self {
+        Self {
+            machine,
+            breakpoints: vec![],
+            trace: vec![],
+        }
+    }
+
+    pub fn machine(&self) -> &Machine<'a> {
+        &self.machine
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// Runs a single cycle, ignoring breakpoints.
+    pub fn step(&mut self) -> Option<&Tick> {
+        let tick = self.machine.step()?;
+        self.trace.push(tick);
+
+        self.trace.last()
+    }
+
+    /// Runs until a cycle hits a breakpoint, or the program ends.
+    pub fn run(&mut self) -> Stop {
+        while self.step().is_some() {
+            let mut recent = self.trace.iter().rev();
+            let (tick, previous) = (recent.next().expect("a cycle just ran"), recent.next());
+
+            if let Some(&breakpoint) = self.breakpoints.iter().find(|bp| bp.hit(previous, tick)) {
+                return Stop::Breakpoint(breakpoint, tick.clone());
+            }
+        }
+
+        Stop::Halted
+    }
+
+    pub fn trace(&self) -> &[Tick] {
+        &self.trace
+    }
+
+    /// Prints the trace, a cycle per line with the running instruction and the registers.
+    pub fn dump_trace(&self) -> String {
+        let set = self.machine.set;
+        let mut dump = String::new();
+
+        for tick in &self.trace {
+            let instruction = set.format(&self.machine.program[tick.pc]);
+            let registers = set
+                .registers
+                .iter()
+                .zip(tick.registers.values())
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let _ = writeln!(
+                dump,
+                "{:>5}  {:>4}  {instruction:<16}  {registers}",
+                tick.cycle, tick.pc
+            );
+        }
+
+        dump
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cpy(registers: &mut Registers, operands: &[Operand]) -> Flow {
+        if let Operand::Register(target) = operands[1] {
+            registers.set(target, registers.value(operands[0]));
+        }
+
+        Flow::Next
+    }
+
+    fn dec(registers: &mut Registers, operands: &[Operand]) -> Flow {
+        if let Operand::Register(register) = operands[0] {
+            registers.set(register, registers.get(register) - 1);
+        }
+
+        Flow::Next
+    }
+
+    fn jnz(registers: &mut Registers, operands: &[Operand]) -> Flow {
+        match registers.value(operands[0]) {
+            0 => Flow::Next,
+            _ => Flow::Jump(registers.value(operands[1])),
+        }
+    }
+
+    /// A cut down version of 2016's assembunny, with a slow `cpy`.
+    static SET: InstructionSet = InstructionSet {
+        registers: &["a", "b"],
+        instructions: &[
+            InstructionSpec {
+                name: "cpy",
+                operands: &[OperandKind::Any, OperandKind::Register],
+                cycles: 2,
+                execute: cpy,
+            },
+            InstructionSpec {
+                name: "dec",
+                operands: &[OperandKind::Register],
+                cycles: 1,
+                execute: dec,
+            },
+            InstructionSpec {
+                name: "jnz",
+                operands: &[OperandKind::Any, OperandKind::Value],
+                cycles: 1,
+                execute: jnz,
+            },
+        ],
+    };
+
+    const PROGRAM: &str = "cpy 3 a
+    dec a
+    jnz a -1
+    cpy a b";
+
+    #[test]
+    fn parse_with_the_table() {
+        let program = SET.parse(PROGRAM).unwrap();
+
+        assert_eq!(
+            vec![Operand::Value(3), Operand::Register(0)],
+            program[0].operands().collect::<Vec<_>>()
+        );
+        assert_eq!("jnz a -1", SET.format(&program[2]));
+        assert_eq!(
+            Err(ParseError::new(
+                2,
+                1,
+                "dec",
+                "expected `cpy <register or value> <register>`, `dec <register>` or `jnz <register or value> <value>`"
+            )),
+            SET.parse("dec a\ndec").map(|program| program.len())
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 5, "c", "expected a register")),
+            SET.parse("dec c").map(|program| program.len())
+        );
+    }
+
+    #[test]
+    fn run_cycles_lazily() {
+        let program = SET.parse(PROGRAM).unwrap();
+        let mut machine = Machine::new(&SET, &program).with_register("b", 7);
+
+        let ticks = machine
+            .by_ref()
+            .map(|tick| (tick.pc, tick.registers.get(0)));
+
+        assert_eq!(
+            vec![
+                (0, 0),
+                (0, 0),
+                (1, 3),
+                (2, 2),
+                (1, 2),
+                (2, 1),
+                (1, 1),
+                (2, 0),
+                (3, 0),
+                (3, 0)
+            ],
+            ticks.collect::<Vec<_>>()
+        );
+        assert!(machine.is_halted());
+        assert_eq!(Some(0), machine.register("b"));
+    }
+
+    #[test]
+    fn break_and_trace() {
+        let program = SET.parse(PROGRAM).unwrap();
+        let mut debugger = Debugger::new(Machine::new(&SET, &program));
+
+        debugger.add_breakpoint(Breakpoint::Register {
+            register: 0,
+            value: 1,
+        });
+        debugger.add_breakpoint(Breakpoint::Cycle(9));
+
+        // `a` is 1 during cycles 6 and 7, so resuming runs on to the next breakpoint.
+        assert!(matches!(
+            debugger.run(),
+            Stop::Breakpoint(Breakpoint::Register { .. }, Tick { cycle: 6, .. })
+        ));
+        assert!(matches!(
+            debugger.run(),
+            Stop::Breakpoint(Breakpoint::Cycle(9), Tick { pc: 3, .. })
+        ));
+        assert_eq!(Stop::Halted, debugger.run());
+        assert_eq!(10, debugger.trace().len());
+        assert_eq!(
+            "    1     0  cpy 3 a           a=0 b=0",
+            debugger.dump_trace().lines().next().unwrap()
+        );
+    }
+
+    #[test]
+    fn break_when_a_register_changes_to_the_value() {
+        let program = SET.parse(PROGRAM).unwrap();
+        let mut debugger = Debugger::new(Machine::new(&SET, &program));
+
+        debugger.add_breakpoint(Breakpoint::Register {
+            register: 0,
+            value: 0,
+        });
+
+        assert_eq!(Some(1), debugger.step().map(|tick| tick.cycle));
+        assert!(matches!(
+            debugger.run(),
+            Stop::Breakpoint(_, Tick { cycle: 8, .. })
+        ));
+        assert_eq!(Stop::Halted, debugger.run());
+    }
+
+    #[test]
+    fn take_any_number_of_operands() {
+        fn sum(registers: &mut Registers, operands: &[Operand]) -> Flow {
+            let total = operands[1..]
+                .iter()
+                .map(|&operand| registers.value(operand))
+                .sum();
+
+            if let Operand::Register(target) = operands[0] {
+                registers.set(target, total);
+            }
+
+            Flow::Next
+        }
+
+        static WIDE: InstructionSet = InstructionSet {
+            registers: &["a"],
+            instructions: &[InstructionSpec {
+                name: "sum",
+                operands: &[
+                    OperandKind::Register,
+                    OperandKind::Value,
+                    OperandKind::Value,
+                    OperandKind::Value,
+                    OperandKind::Value,
+                ],
+                cycles: 1,
+                execute: sum,
+            }],
+        };
+
+        let program = WIDE.parse("sum a 1 2 3 4").unwrap();
+        let mut machine = Machine::new(&WIDE, &program);
+        machine.by_ref().for_each(drop);
+
+        assert_eq!("sum a 1 2 3 4", WIDE.format(&program[0]));
+        assert_eq!(Some(10), machine.register("a"));
+    }
+}